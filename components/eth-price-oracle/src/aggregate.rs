@@ -0,0 +1,92 @@
+use crate::sources::Quote;
+
+/// The consensus price across several sources.
+#[derive(Debug, Clone)]
+pub struct Aggregate {
+    pub price: f64,
+    /// Oldest timestamp among the agreeing quotes
+    pub timestamp: u64,
+    /// Number of quotes within the outlier band around the median
+    pub agreed: u32,
+    /// Largest distance between an agreeing quote and `price`
+    pub spread: f64,
+}
+
+impl Aggregate {
+    /// Cross rate of two aggregates quoted in the same currency, e.g. ETH/USD and EUR/USD into
+    /// ETH/EUR. The relative spreads add up.
+    pub fn cross(&self, rate: &Aggregate) -> Aggregate {
+        let price = self.price / rate.price;
+        Aggregate {
+            price,
+            timestamp: self.timestamp.min(rate.timestamp),
+            agreed: self.agreed.min(rate.agreed),
+            spread: price * (self.spread / self.price + rate.spread / rate.price),
+        }
+    }
+}
+
+/// Takes the median of all quotes, drops the ones further than `band_bps` from it and returns
+/// the median of the remaining quotes. Fails when fewer than `min_sources` quotes agree.
+pub fn aggregate(quotes: &[Quote], band_bps: u32, min_sources: u32) -> Result<Aggregate, String> {
+    let reference =
+        median(quotes.iter().map(|q| q.price).collect()).ok_or("No source returned a quote")?;
+    let band = reference * band_bps as f64 / 10_000.0;
+
+    let (agreeing, outliers): (Vec<&Quote>, Vec<&Quote>) =
+        quotes.iter().partition(|q| (q.price - reference).abs() <= band);
+    for outlier in &outliers {
+        eprintln!(
+            "Dropping {} quote {} (median {}, band {} bps)",
+            outlier.source, outlier.price, reference, band_bps
+        );
+    }
+
+    let agreed = agreeing.len() as u32;
+    if agreed < min_sources {
+        return Err(format!(
+            "only {} of {} sources agreed within {} bps, {} required",
+            agreed,
+            quotes.len(),
+            band_bps,
+            min_sources
+        ));
+    }
+
+    let price =
+        median(agreeing.iter().map(|q| q.price).collect()).ok_or("No source returned a quote")?;
+    Ok(Aggregate {
+        price,
+        timestamp: agreeing.iter().map(|q| q.timestamp).min().unwrap_or_default(),
+        agreed,
+        spread: agreeing.iter().map(|q| (q.price - price).abs()).fold(0.0, f64::max),
+    })
+}
+
+fn median(mut values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        Some((values[mid - 1] + values[mid]) / 2.0)
+    } else {
+        Some(values[mid])
+    }
+}
+
+/// Drops the quotes older than `max_age_secs`.
+pub fn drop_stale(quotes: Vec<Quote>, max_age_secs: u64, now: u64) -> Vec<Quote> {
+    quotes
+        .into_iter()
+        .filter(|quote| {
+            let age = now.saturating_sub(quote.timestamp);
+            if age > max_age_secs {
+                eprintln!("Dropping stale {} quote from {}s ago", quote.source, age);
+            }
+            age <= max_age_secs
+        })
+        .collect()
+}
@@ -0,0 +1,62 @@
+//! Confidence band around the reported price, in the style of Pyth's `price ± conf`.
+use crate::sources::coinmarketcap;
+use crate::volatility::log_return_std;
+use serde::{Deserialize, Serialize};
+
+/// Lookback used by [`ConfidenceMethod::ChartVolatility`].
+const VOLATILITY_WINDOW_SECS: u64 = 60 * 60;
+
+/// How the confidence band was derived, recorded next to it in the output.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfidenceMethod {
+    /// Largest distance between an agreeing source and the median
+    SourceSpread,
+    /// One standard deviation of the chart's log returns over the last hour
+    ChartVolatility,
+}
+
+impl ConfidenceMethod {
+    /// Value of `PriceData.confMethod`.
+    pub fn id(&self) -> u8 {
+        match self {
+            ConfidenceMethod::SourceSpread => 0,
+            ConfidenceMethod::ChartVolatility => 1,
+        }
+    }
+
+    pub fn from_id(id: u8) -> Result<Self, String> {
+        match id {
+            0 => Ok(ConfidenceMethod::SourceSpread),
+            1 => Ok(ConfidenceMethod::ChartVolatility),
+            _ => Err(format!("Unknown confidence method {}", id)),
+        }
+    }
+}
+
+impl std::str::FromStr for ConfidenceMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "spread" => Ok(ConfidenceMethod::SourceSpread),
+            "volatility" => Ok(ConfidenceMethod::ChartVolatility),
+            _ => Err(format!("Invalid confidence method '{}', expected spread or volatility", s)),
+        }
+    }
+}
+
+/// Confidence band in USD around `price` from the recent volatility of the asset.
+pub async fn chart_confidence(id: u64, price: f64) -> Result<f64, String> {
+    let points = coinmarketcap::fetch_chart(id, "1D").await?;
+    let latest = points.last().map(|(timestamp, _)| *timestamp).ok_or("Empty chart data")?;
+    let window_start = latest.saturating_sub(VOLATILITY_WINDOW_SECS);
+
+    let prices: Vec<f64> = points
+        .into_iter()
+        .filter(|(timestamp, _)| *timestamp >= window_start)
+        .map(|(_, price)| price)
+        .collect();
+    let std = log_return_std(&prices).ok_or("Not enough chart points for a confidence band")?;
+    Ok(price * std)
+}
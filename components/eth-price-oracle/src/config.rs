@@ -0,0 +1,151 @@
+use crate::confidence::ConfidenceMethod;
+use crate::sources::{DexPool, DEFAULT_SOURCES};
+use std::collections::HashMap;
+use std::str::FromStr;
+use wavs_wasi_chain::ethereum::alloy_primitives::Address;
+
+/// Decimals used by Chainlink USD feeds, a sensible default for on-chain consumers.
+pub const DEFAULT_PRICE_DECIMALS: u8 = 8;
+const MAX_PRICE_DECIMALS: u8 = 18;
+/// Quotes further than 1% from the median are treated as outliers.
+pub const DEFAULT_OUTLIER_BAND_BPS: u32 = 100;
+pub const DEFAULT_MIN_SOURCES: u32 = 2;
+pub const DEFAULT_MAX_PRICE_AGE_SECS: u64 = 5 * 60;
+pub const DEFAULT_SUBMIT_CHAIN: &str = "local";
+pub const DEFAULT_DEVIATION_BPS: u32 = 50;
+pub const DEFAULT_HEARTBEAT_SECS: u64 = 60 * 60;
+pub const DEFAULT_HISTORICAL_TOLERANCE_SECS: u64 = 15 * 60;
+pub const DEFAULT_DEX_CHAIN: &str = "local";
+
+/// Operator configuration, read from the `host_envs` made available to the component.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Decimals used to scale prices into integers for `Destination::Ethereum`
+    pub price_decimals: u8,
+    /// Sources to query, see [`DEFAULT_SOURCES`]
+    pub price_sources: Vec<String>,
+    /// Maximum distance from the median, in basis points, for a quote to count as agreeing
+    pub outlier_band_bps: u32,
+    /// Minimum number of agreeing sources required to report a price
+    pub min_sources: u32,
+    /// Quotes older than this are rejected
+    pub max_price_age_secs: u64,
+    /// `SimpleSubmit` holding the previous prices. Every update is submitted when unset
+    pub submit_address: Option<Address>,
+    /// Chain of `submit_address`, as named in `wavs.toml`
+    pub submit_chain: String,
+    /// Minimum price change, in basis points, to submit before the heartbeat
+    pub deviation_bps: u32,
+    /// Maximum time between two submissions
+    pub heartbeat_secs: u64,
+    /// Default maximum distance between a historical sample and the requested timestamp
+    pub historical_tolerance_secs: u64,
+    /// Pools read by the `dex` source
+    pub dex_pools: Vec<DexPool>,
+    /// Chain of `dex_pools`, as named in `wavs.toml`
+    pub dex_chain: String,
+    /// How the confidence band of spot prices is derived
+    pub confidence_method: ConfidenceMethod,
+    /// CoinGecko ids keyed by CoinMarketCap id, for assets the `coingecko` source can't match
+    pub coingecko_ids: HashMap<u64, String>,
+}
+
+impl Config {
+    pub fn from_env() -> Result<Self, String> {
+        let price_decimals = env_or("WAVS_ENV_PRICE_DECIMALS", DEFAULT_PRICE_DECIMALS)?;
+        if price_decimals > MAX_PRICE_DECIMALS {
+            return Err(format!(
+                "WAVS_ENV_PRICE_DECIMALS must be at most {}, got {}",
+                MAX_PRICE_DECIMALS, price_decimals
+            ));
+        }
+
+        let price_sources: Vec<String> = match std::env::var("WAVS_ENV_PRICE_SOURCES") {
+            Ok(value) => value
+                .split(',')
+                .map(|name| name.trim().to_lowercase())
+                .filter(|name| !name.is_empty())
+                .collect(),
+            Err(_) => DEFAULT_SOURCES.iter().map(|name| name.to_string()).collect(),
+        };
+        let outlier_band_bps = env_or("WAVS_ENV_OUTLIER_BAND_BPS", DEFAULT_OUTLIER_BAND_BPS)?;
+        let min_sources = env_or("WAVS_ENV_MIN_SOURCES", DEFAULT_MIN_SOURCES)?;
+        if min_sources == 0 || min_sources as usize > price_sources.len() {
+            return Err(format!(
+                "WAVS_ENV_MIN_SOURCES must be between 1 and the {} enabled sources, got {}",
+                price_sources.len(),
+                min_sources
+            ));
+        }
+
+        let submit_address = match std::env::var("WAVS_ENV_SUBMIT_ADDRESS") {
+            Ok(value) => Some(
+                value
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("Invalid WAVS_ENV_SUBMIT_ADDRESS: '{}'", value))?,
+            ),
+            Err(_) => None,
+        };
+
+        let dex_pools = match std::env::var("WAVS_ENV_DEX_POOLS") {
+            Ok(value) => value
+                .split(',')
+                .filter(|pool| !pool.trim().is_empty())
+                .map(|pool| pool.trim().parse::<DexPool>())
+                .collect::<Result<Vec<_>, _>>()?,
+            Err(_) => Vec::new(),
+        };
+        if price_sources.iter().any(|name| name == "dex") && dex_pools.is_empty() {
+            return Err("The dex source needs WAVS_ENV_DEX_POOLS".to_string());
+        }
+
+        let coingecko_ids = match std::env::var("WAVS_ENV_COINGECKO_IDS") {
+            Ok(value) => value
+                .split(',')
+                .filter(|pair| !pair.trim().is_empty())
+                .map(|pair| {
+                    pair.split_once('=')
+                        .and_then(|(id, coin)| Some((id.trim().parse().ok()?, coin.trim())))
+                        .filter(|(_, coin)| !coin.is_empty())
+                        .map(|(id, coin)| (id, coin.to_string()))
+                        .ok_or_else(|| format!("Invalid WAVS_ENV_COINGECKO_IDS entry '{}'", pair))
+                })
+                .collect::<Result<_, _>>()?,
+            Err(_) => HashMap::new(),
+        };
+
+        Ok(Self {
+            price_decimals,
+            price_sources,
+            outlier_band_bps,
+            min_sources,
+            max_price_age_secs: env_or("WAVS_ENV_MAX_PRICE_AGE_SECS", DEFAULT_MAX_PRICE_AGE_SECS)?,
+            submit_address,
+            submit_chain: env_or("WAVS_ENV_SUBMIT_CHAIN", DEFAULT_SUBMIT_CHAIN.to_string())?,
+            deviation_bps: env_or("WAVS_ENV_DEVIATION_BPS", DEFAULT_DEVIATION_BPS)?,
+            heartbeat_secs: env_or("WAVS_ENV_HEARTBEAT_SECS", DEFAULT_HEARTBEAT_SECS)?,
+            historical_tolerance_secs: env_or(
+                "WAVS_ENV_HISTORICAL_TOLERANCE_SECS",
+                DEFAULT_HISTORICAL_TOLERANCE_SECS,
+            )?,
+            dex_pools,
+            dex_chain: env_or("WAVS_ENV_DEX_CHAIN", DEFAULT_DEX_CHAIN.to_string())?,
+            confidence_method: env_or(
+                "WAVS_ENV_CONFIDENCE_METHOD",
+                ConfidenceMethod::SourceSpread,
+            )?,
+            coingecko_ids,
+        })
+    }
+}
+
+/// Reads and parses an environment variable, falling back to `default` when unset.
+fn env_or<T: FromStr>(key: &str, default: T) -> Result<T, String> {
+    match std::env::var(key) {
+        Ok(value) => {
+            value.trim().parse().map_err(|_| format!("Invalid value for {}: '{}'", key, value))
+        }
+        Err(_) => Ok(default),
+    }
+}
@@ -0,0 +1,44 @@
+//! Minimal JSON-RPC client for reading contracts on the chains configured in `wavs.toml`.
+use crate::bindings::host::get_eth_chain_config;
+use serde::Deserialize;
+use wavs_wasi_chain::ethereum::alloy_primitives::{hex, Address};
+use wavs_wasi_chain::http::{fetch_json, http_request_post_json};
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse {
+    result: Option<String>,
+    error: Option<JsonRpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+/// HTTP endpoint of a chain from the operator's `wavs.toml`, e.g. `local`.
+pub fn http_endpoint(chain_name: &str) -> Result<String, String> {
+    get_eth_chain_config(chain_name)
+        .ok_or_else(|| format!("Chain '{}' is not configured", chain_name))?
+        .http_endpoint
+        .ok_or_else(|| format!("Chain '{}' has no http_endpoint", chain_name))
+}
+
+/// Executes an `eth_call` against the latest block and returns the raw return data.
+pub async fn eth_call(endpoint: &str, to: Address, data: Vec<u8>) -> Result<Vec<u8>, String> {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "eth_call",
+        "params": [{ "to": to.to_string(), "data": hex::encode_prefixed(data) }, "latest"],
+    });
+
+    let req = http_request_post_json(endpoint, &body).map_err(|e| e.to_string())?;
+    let resp: JsonRpcResponse = fetch_json(req).await.map_err(|e| e.to_string())?;
+
+    if let Some(error) = resp.error {
+        return Err(format!("eth_call failed ({}): {}", error.code, error.message));
+    }
+    let result = resp.result.ok_or("eth_call returned no result")?;
+    hex::decode(result).map_err(|e| e.to_string())
+}
@@ -0,0 +1,67 @@
+use crate::sources::{coinmarketcap, unix_now};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HistoricalPrice {
+    /// CoinMarketCap id the request resolved to
+    pub id: u64,
+    pub symbol: String,
+    /// Price in USD of the sample closest to the target
+    pub price: f64,
+    /// Unix timestamp (seconds) that was requested
+    pub target_timestamp: u64,
+    /// Unix timestamp (seconds) of the sample used
+    pub sample_timestamp: u64,
+    /// Distance in seconds between the sample and the target
+    pub distance: u64,
+}
+
+/// Finds the historical quote closest to `target`, rejecting it if further than `tolerance_secs`.
+pub async fn get_price_at(
+    id: u64,
+    target: u64,
+    tolerance_secs: u64,
+) -> Result<HistoricalPrice, String> {
+    let now = unix_now();
+    if target > now {
+        return Err(format!("Timestamp {} is in the future", target));
+    }
+
+    let asset = coinmarketcap::fetch_asset(id).await?;
+    let points = coinmarketcap::fetch_chart(id, chart_range_for_age(now - target)).await?;
+
+    let (sample_timestamp, price) = points
+        .into_iter()
+        .min_by_key(|(timestamp, _)| timestamp.abs_diff(target))
+        .ok_or("Empty chart data")?;
+    let distance = sample_timestamp.abs_diff(target);
+    if distance > tolerance_secs {
+        return Err(format!(
+            "Closest {} sample is {}s away from {}, tolerance is {}s",
+            asset.symbol, distance, target, tolerance_secs
+        ));
+    }
+
+    Ok(HistoricalPrice {
+        id,
+        symbol: asset.symbol,
+        price,
+        target_timestamp: target,
+        sample_timestamp,
+        distance,
+    })
+}
+
+/// Smallest CoinMarketCap chart range going back `age` seconds. Shorter ranges have a finer
+/// granularity, from 5 minutes for `1D` up to daily points for `ALL`.
+fn chart_range_for_age(age: u64) -> &'static str {
+    const DAY: u64 = 24 * 60 * 60;
+    match age {
+        a if a <= DAY => "1D",
+        a if a <= 7 * DAY => "7D",
+        a if a <= 30 * DAY => "1M",
+        a if a <= 90 * DAY => "3M",
+        a if a <= 365 * DAY => "1Y",
+        _ => "ALL",
+    }
+}
@@ -1,10 +1,32 @@
+mod aggregate;
+mod confidence;
+mod config;
+mod eth;
+mod historical;
+mod policy;
+mod request;
+mod resolution;
+mod sources;
+mod stats;
 mod trigger;
+mod twap;
+mod volatility;
+use aggregate::{aggregate, drop_stale, Aggregate};
+use confidence::{chart_confidence, ConfidenceMethod};
+use config::Config;
+use historical::{get_price_at, HistoricalPrice};
+use request::{Mode, PriceRequest};
+use resolution::{resolve_threshold_market, MarketResolution};
+use sources::{coinmarketcap, currency_asset, unix_now, PriceSource, USD};
+use stats::{get_stats, MarketStats};
 use trigger::{decode_trigger_event, encode_trigger_output, Destination};
-use wavs_wasi_chain::http::{fetch_json, http_request_get};
+use twap::{get_twap, TwapData};
+use volatility::{get_realized_volatility, VolatilityData};
 pub mod bindings;
 use crate::bindings::{export, Guest, TriggerAction};
+use futures::future::{try_join, try_join_all};
 use serde::{Deserialize, Serialize};
-use wstd::{http::HeaderValue, runtime::block_on};
+use wstd::runtime::block_on;
 
 struct Component;
 export!(Component with_types_in bindings);
@@ -14,107 +36,186 @@ impl Guest for Component {
         let (trigger_id, req, dest) =
             decode_trigger_event(action.data).map_err(|e| e.to_string())?;
 
-        // Convert bytes to string and parse first char as u64
+        // Convert bytes to string and parse the request, e.g. "BTC,1027|mode=twap|window=24h"
         let input = std::str::from_utf8(&req).map_err(|e| e.to_string())?;
-        println!("input id: {}", input);
-
-        let id = input.chars().next().ok_or("Empty input")?;
-        let id = id.to_digit(16).ok_or("Invalid hex digit")? as u64;
-
-        let res = block_on(async move {
-            let resp_data = get_price_feed(id).await?;
+        println!("input: {}", input);
+
+        let request = PriceRequest::parse(input)?;
+        let config = Config::from_env()?;
+
+        let sources = sources::from_config(&config)?;
+
+        let res = block_on(async {
+            let asset_ids = coinmarketcap::resolve_ids(&request.assets).await?;
+            let ids = asset_ids.iter().copied();
+            let resp_data = match request.mode.clone() {
+                Mode::Spot => Output::Prices(
+                    try_join_all(
+                        ids.map(|id| get_price_feed(id, &request.convert, &sources, &config)),
+                    )
+                    .await?,
+                ),
+                Mode::Twap { window } => {
+                    Output::Twap(try_join_all(ids.map(|id| get_twap(id, window))).await?)
+                }
+                Mode::AggregatorV3 => {
+                    let feed =
+                        get_price_feed(asset_ids[0], &request.convert, &sources, &config).await?;
+                    Output::Round(RoundData::new(trigger_id, &feed, unix_now()))
+                }
+                Mode::Historical { at, tolerance } => {
+                    let tolerance = tolerance.unwrap_or(config.historical_tolerance_secs);
+                    Output::Historical(
+                        try_join_all(ids.map(|id| get_price_at(id, at, tolerance))).await?,
+                    )
+                }
+                Mode::Resolve { market, tolerance } => {
+                    let tolerance = tolerance.unwrap_or(config.historical_tolerance_secs);
+                    Output::Resolution(
+                        resolve_threshold_market(
+                            asset_ids[0],
+                            &market,
+                            tolerance,
+                            config.price_decimals,
+                            unix_now(),
+                        )
+                        .await?,
+                    )
+                }
+                Mode::Stats { fields } => {
+                    Output::Stats(try_join_all(ids.map(|id| get_stats(id, &fields))).await?)
+                }
+                Mode::Volatility { window } => Output::Volatility(
+                    try_join_all(ids.map(|id| get_realized_volatility(id, window))).await?,
+                ),
+            };
             println!("resp_data: {:?}", resp_data);
-            serde_json::to_vec(&resp_data).map_err(|e| e.to_string())
+            Ok::<_, String>(resp_data)
         })?;
 
+        if let (Destination::Ethereum, Output::Prices(feeds)) = (&dest, &res) {
+            if !block_on(policy::should_submit(feeds, &config, unix_now())) {
+                println!("Prices within deviation threshold and heartbeat, skipping submission");
+                return Ok(None);
+            }
+        }
+
         let output = match dest {
-            Destination::Ethereum => Some(encode_trigger_output(trigger_id, &res)),
-            Destination::CliOutput => Some(res),
+            Destination::Ethereum => encode_trigger_output(trigger_id, &res, config.price_decimals)
+                .map_err(|e| e.to_string())?,
+            Destination::CliOutput => serde_json::to_vec(&res).map_err(|e| e.to_string())?,
         };
-        Ok(output)
+        Ok(Some(output))
     }
 }
 
-async fn get_price_feed(id: u64) -> Result<PriceFeedData, String> {
-    let url = format!(
-        "https://api.coinmarketcap.com/data-api/v3/cryptocurrency/detail?id={}&range=1h",
-        id
-    );
-
-    let current_time = std::time::SystemTime::now().elapsed().unwrap().as_secs();
-
-    let mut req = http_request_get(&url).map_err(|e| e.to_string())?;
-    req.headers_mut().insert("Accept", HeaderValue::from_static("application/json"));
-    req.headers_mut().insert("Content-Type", HeaderValue::from_static("application/json"));
-    req.headers_mut()
-        .insert("User-Agent", HeaderValue::from_static("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/132.0.0.0 Safari/537.36"));
-    req.headers_mut().insert(
-        "Cookie",
-        HeaderValue::from_str(&format!("myrandom_cookie={}", current_time)).unwrap(),
-    );
-
-    let json: Root = fetch_json(req).await.map_err(|e| e.to_string())?;
-
-    Ok(PriceFeedData {
-        symbol: json.data.symbol,
-        price: json.data.statistics.price,
-        timestamp: json.status.timestamp,
-    })
+/// The result of a request, one entry per requested asset.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum Output {
+    Prices(Vec<PriceFeedData>),
+    Twap(Vec<TwapData>),
+    Round(RoundData),
+    Historical(Vec<HistoricalPrice>),
+    Resolution(MarketResolution),
+    Stats(Vec<MarketStats>),
+    Volatility(Vec<VolatilityData>),
 }
 
+/// A Chainlink `AggregatorV3Interface` round, keyed by trigger id.
 #[derive(Debug, Serialize, Deserialize)]
-pub struct PriceFeedData {
-    symbol: String,
-    timestamp: String,
-    price: f64,
+pub struct RoundData {
+    round_id: u64,
+    /// Price in the `convert` currency, USD by default, scaled to the configured decimals when
+    /// encoded
+    answer: f64,
+    /// Unix timestamp (seconds) of the oldest quote used
+    started_at: u64,
+    /// Unix timestamp (seconds) at which the quotes were aggregated
+    updated_at: u64,
+    answered_in_round: u64,
 }
 
-/// -----
-/// <https://transform.tools/json-to-rust-serde>
-/// Generated from <https://api.coinmarketcap.com/data-api/v3/cryptocurrency/detail?id=1&range=1h>
-/// -----
-///
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct Root {
-    pub data: Data,
-    pub status: Status,
+impl RoundData {
+    fn new(trigger_id: u64, feed: &PriceFeedData, now: u64) -> Self {
+        Self {
+            round_id: trigger_id,
+            answer: feed.price,
+            started_at: feed.timestamp,
+            updated_at: now,
+            answered_in_round: trigger_id,
+        }
+    }
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct Data {
-    pub id: f64,
-    pub name: String,
-    pub symbol: String,
-    pub statistics: Statistics,
-    pub description: String,
-    pub category: String,
-    pub slug: String,
-}
+/// Price of an asset in `currency`. Pairs without enough direct quotes are derived from the
+/// USD prices of the asset and of the currency.
+async fn get_price_feed(
+    id: u64,
+    currency: &str,
+    sources: &[Box<dyn PriceSource>],
+    config: &Config,
+) -> Result<PriceFeedData, String> {
+    let asset = coinmarketcap::fetch_asset(id).await?;
+    let (aggregate, derived) = match aggregate_quotes(&asset, currency, sources, config).await {
+        Ok(aggregate) => (aggregate, false),
+        Err(e) if currency != USD => {
+            eprintln!("{}, deriving the cross rate through {}", e, USD);
+            let (price, rate) = try_join(aggregate_quotes(&asset, USD, sources, config), async {
+                let currency = currency_asset(currency).await?;
+                aggregate_quotes(&currency, USD, sources, config).await
+            })
+            .await?;
+            (price.cross(&rate), true)
+        }
+        Err(e) => return Err(e),
+    };
+    let confidence = match config.confidence_method {
+        ConfidenceMethod::SourceSpread => aggregate.spread,
+        ConfidenceMethod::ChartVolatility => chart_confidence(id, aggregate.price).await?,
+    };
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct Statistics {
-    pub price: f64,
-    #[serde(rename = "totalSupply")]
-    pub total_supply: f64,
+    Ok(PriceFeedData {
+        id: asset.id,
+        symbol: asset.symbol,
+        currency: currency.to_string(),
+        derived,
+        price: aggregate.price,
+        confidence,
+        confidence_method: config.confidence_method,
+        timestamp: aggregate.timestamp,
+        sources: aggregate.agreed,
+    })
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct CoinBitesVideo {
-    pub id: String,
-    pub category: String,
-    #[serde(rename = "videoUrl")]
-    pub video_url: String,
-    pub title: String,
-    pub description: String,
-    #[serde(rename = "previewImage")]
-    pub preview_image: String,
+async fn aggregate_quotes(
+    asset: &sources::Asset,
+    currency: &str,
+    sources: &[Box<dyn PriceSource>],
+    config: &Config,
+) -> Result<Aggregate, String> {
+    let quotes = sources::fetch_quotes(sources, asset, currency).await;
+    let quotes = drop_stale(quotes, config.max_price_age_secs, unix_now());
+    aggregate(&quotes, config.outlier_band_bps, config.min_sources)
+        .map_err(|e| format!("{}/{}: {}", asset.symbol, currency, e))
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct Status {
-    pub timestamp: String,
-    pub error_code: String,
-    pub error_message: String,
-    pub elapsed: String,
-    pub credit_count: f64,
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PriceFeedData {
+    /// CoinMarketCap id the request resolved to
+    id: u64,
+    symbol: String,
+    /// Quote currency, e.g. `USD`
+    currency: String,
+    /// Whether the price is a cross rate through USD rather than quoted directly
+    derived: bool,
+    /// Unix timestamp (seconds) of the oldest quote used
+    timestamp: u64,
+    /// Median price across the agreeing sources
+    price: f64,
+    /// Half-width of the band the price is expected to lie in
+    confidence: f64,
+    confidence_method: ConfidenceMethod,
+    /// Number of sources within the outlier band around the median
+    sources: u32,
 }
@@ -0,0 +1,131 @@
+//! Chainlink-style update policy: a new round is only submitted when the price moved past the
+//! deviation threshold or the heartbeat interval elapsed since the previous submission.
+use crate::config::Config;
+use crate::eth::{eth_call, http_endpoint};
+use crate::trigger::decode_prices;
+use crate::PriceFeedData;
+use alloy_sol_macro::sol;
+use alloy_sol_types::SolCall;
+use wavs_wasi_chain::ethereum::alloy_primitives::Address;
+
+sol! {
+    interface ISimpleSubmit {
+        function latestTriggerId() external view returns (uint64);
+        function getData(uint64 triggerId) external view returns (bytes memory);
+        function getSubmittedAt(uint64 triggerId) external view returns (uint64);
+    }
+}
+
+/// Trigger ids read back from the latest submission to find the previous price of each feed
+const MAX_LOOKBACK: u64 = 32;
+
+/// A price stored in `SimpleSubmit` and when it was submitted.
+struct Previous {
+    feed: PriceFeedData,
+    /// Unix timestamp (seconds) of the block that stored the price
+    submitted_at: u64,
+}
+
+/// Whether the feeds should be submitted given the last prices stored in `SimpleSubmit`.
+///
+/// Always true when no submission contract is configured or its state can't be read.
+pub async fn should_submit(feeds: &[PriceFeedData], config: &Config, now: u64) -> bool {
+    let Some(submit_address) = config.submit_address else {
+        return true;
+    };
+
+    match previous_prices(&config.submit_chain, submit_address, feeds).await {
+        Ok(previous) => {
+            needs_update(feeds, &previous, config.deviation_bps, config.heartbeat_secs, now)
+        }
+        Err(e) => {
+            eprintln!("Could not read previous prices, submitting: {}", e);
+            true
+        }
+    }
+}
+
+/// The latest submitted price of each feed. Triggers with different assets, currencies or modes
+/// can share one `SimpleSubmit`, so submissions are read back from the latest one until every
+/// feed is found, skipping those that aren't spot prices, for at most [`MAX_LOOKBACK`] trigger
+/// ids. Feeds not found have no previous price.
+async fn previous_prices(
+    chain_name: &str,
+    address: Address,
+    feeds: &[PriceFeedData],
+) -> Result<Vec<Previous>, String> {
+    let endpoint = http_endpoint(chain_name)?;
+
+    let call = ISimpleSubmit::latestTriggerIdCall {};
+    let data = eth_call(&endpoint, address, call.abi_encode()).await?;
+    let latest = ISimpleSubmit::latestTriggerIdCall::abi_decode_returns(&data, true)
+        .map_err(|e| e.to_string())?
+        ._0;
+
+    let mut previous: Vec<Previous> = Vec::new();
+    for trigger_id in (1..=latest).rev().take(MAX_LOOKBACK as usize) {
+        if feeds.iter().all(|feed| find(&previous, feed).is_some()) {
+            break;
+        }
+
+        let call = ISimpleSubmit::getDataCall { triggerId: trigger_id };
+        let data = eth_call(&endpoint, address, call.abi_encode()).await?;
+        let data = ISimpleSubmit::getDataCall::abi_decode_returns(&data, true)
+            .map_err(|e| e.to_string())?
+            ._0;
+        // Trigger ids handled by another contract, or submissions of another mode
+        let Ok(prices) = decode_prices(&data) else {
+            continue;
+        };
+        let prices: Vec<_> = prices
+            .into_iter()
+            .filter(|price| {
+                find(&previous, price).is_none() && feeds.iter().any(|feed| same_feed(feed, price))
+            })
+            .collect();
+        if prices.is_empty() {
+            continue;
+        }
+
+        let call = ISimpleSubmit::getSubmittedAtCall { triggerId: trigger_id };
+        let data = eth_call(&endpoint, address, call.abi_encode()).await?;
+        let submitted_at = ISimpleSubmit::getSubmittedAtCall::abi_decode_returns(&data, true)
+            .map_err(|e| e.to_string())?
+            ._0;
+        previous.extend(prices.into_iter().map(|feed| Previous { feed, submitted_at }));
+    }
+
+    Ok(previous)
+}
+
+fn same_feed(a: &PriceFeedData, b: &PriceFeedData) -> bool {
+    a.symbol == b.symbol && a.currency == b.currency
+}
+
+fn find<'a>(previous: &'a [Previous], feed: &PriceFeedData) -> Option<&'a Previous> {
+    previous.iter().find(|prev| same_feed(&prev.feed, feed))
+}
+
+/// True if any feed deviated past `deviation_bps` from its previous price, was last submitted
+/// longer than the heartbeat ago, or has no previous price at all.
+fn needs_update(
+    feeds: &[PriceFeedData],
+    previous: &[Previous],
+    deviation_bps: u32,
+    heartbeat_secs: u64,
+    now: u64,
+) -> bool {
+    feeds.iter().any(|feed| {
+        let Some(prev) = find(previous, feed) else {
+            return true;
+        };
+
+        let deviation = if prev.feed.price > 0.0 {
+            (feed.price - prev.feed.price).abs() / prev.feed.price * 10_000.0
+        } else {
+            f64::INFINITY
+        };
+
+        deviation >= deviation_bps as f64 || now.saturating_sub(prev.submitted_at) >= heartbeat_secs
+    })
+}
@@ -0,0 +1,218 @@
+use crate::resolution::{Comparator, ThresholdMarket};
+use crate::sources::USD;
+use crate::stats::StatField;
+
+/// A trigger input of the form `ASSETS[|key=value...]`, e.g. `1,1027|mode=twap|window=24h`.
+///
+/// `ASSETS` is a comma separated list of CoinMarketCap ids, symbols or slugs, e.g.
+/// `1,ETH,tether`. Without options the USD spot price of every asset is reported.
+#[derive(Debug, Clone)]
+pub struct PriceRequest {
+    pub assets: Vec<AssetQuery>,
+    pub mode: Mode,
+    /// Currency prices are quoted in, e.g. `EUR` or `BTC`
+    pub convert: String,
+}
+
+/// An asset as written in the trigger input.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AssetQuery {
+    /// CoinMarketCap id, e.g. `1027`
+    Id(u64),
+    /// Symbol or slug, e.g. `ETH` or `ethereum`. `SYMBOL:ID` (e.g. `ETH:1027`) pins the id of an
+    /// ambiguous symbol, otherwise the best ranked listing is used.
+    Name { name: String, id: Option<u64> },
+}
+
+#[derive(Debug, Clone)]
+pub enum Mode {
+    /// Aggregated spot price across all enabled sources
+    Spot,
+    /// Time-weighted average price over the window
+    Twap { window: Window },
+    /// Spot price of a single asset as a Chainlink `AggregatorV3Interface` round
+    AggregatorV3,
+    /// Price closest to a unix timestamp, within `tolerance` seconds
+    Historical { at: u64, tolerance: Option<u64> },
+    /// Settles a binary market on the price of a single asset at `market.settlement_time`
+    Resolve { market: ThresholdMarket, tolerance: Option<u64> },
+    /// Spot price with the selected market statistics
+    Stats { fields: Vec<StatField> },
+    /// Annualized realized volatility over the window
+    Volatility { window: Window },
+}
+
+/// Lookback window for modes working on historical data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Window {
+    OneHour,
+    OneDay,
+    SevenDays,
+    ThirtyDays,
+}
+
+impl Window {
+    pub fn seconds(&self) -> u64 {
+        match self {
+            Window::OneHour => 60 * 60,
+            Window::OneDay => 24 * 60 * 60,
+            Window::SevenDays => 7 * 24 * 60 * 60,
+            Window::ThirtyDays => 30 * 24 * 60 * 60,
+        }
+    }
+
+    /// Smallest CoinMarketCap chart range covering the window.
+    pub fn chart_range(&self) -> &'static str {
+        match self {
+            Window::OneHour | Window::OneDay => "1D",
+            Window::SevenDays => "7D",
+            Window::ThirtyDays => "1M",
+        }
+    }
+}
+
+impl std::str::FromStr for Window {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1h" => Ok(Window::OneHour),
+            "24h" | "1d" => Ok(Window::OneDay),
+            "7d" => Ok(Window::SevenDays),
+            "30d" => Ok(Window::ThirtyDays),
+            _ => Err(format!("Invalid window '{}', expected 1h, 24h, 7d or 30d", s)),
+        }
+    }
+}
+
+impl PriceRequest {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        // Trailing NUL bytes are ignored so `bytes32` encoded strings keep working
+        let input = input.trim_end_matches('\0').trim();
+        let mut parts = input.split('|');
+
+        let assets = parse_assets(parts.next().unwrap_or_default())?;
+
+        let mut mode = None;
+        let mut window = None;
+        let mut at = None;
+        let mut tolerance = None;
+        let mut comparator = None;
+        let mut threshold = None;
+        let mut question_id = None;
+        let mut fields = None;
+        let mut convert = USD.to_string();
+        for option in parts {
+            let (key, value) = option
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid option '{}', expected key=value", option))?;
+            match key.trim() {
+                "mode" => mode = Some(value.trim().to_string()),
+                "window" => window = Some(value.trim().parse::<Window>()?),
+                "at" => at = Some(parse_number(key, value)?),
+                "tolerance" => tolerance = Some(parse_number(key, value)?),
+                "comparator" => comparator = Some(value.trim().parse::<Comparator>()?),
+                "threshold" => {
+                    threshold = Some(
+                        value
+                            .trim()
+                            .parse::<f64>()
+                            .map_err(|_| format!("Invalid threshold: '{}'", value))?,
+                    )
+                }
+                "question" => {
+                    question_id = Some(
+                        value
+                            .trim()
+                            .parse()
+                            .map_err(|_| format!("Invalid question: '{}'", value))?,
+                    )
+                }
+                "fields" => fields = Some(parse_fields(value)?),
+                "convert" => convert = parse_currency(value)?,
+                other => return Err(format!("Unknown option '{}'", other)),
+            }
+        }
+
+        let mode = match mode.as_deref().unwrap_or("spot") {
+            "spot" => Mode::Spot,
+            "twap" => Mode::Twap { window: window.unwrap_or(Window::OneHour) },
+            "aggregator_v3" if assets.len() == 1 => Mode::AggregatorV3,
+            "aggregator_v3" => return Err("aggregator_v3 expects a single asset".to_string()),
+            "historical" => Mode::Historical {
+                at: at.ok_or("historical mode expects an 'at' unix timestamp")?,
+                tolerance,
+            },
+            "resolve" if assets.len() == 1 => Mode::Resolve {
+                market: ThresholdMarket {
+                    question_id: question_id.ok_or("resolve mode expects a bytes32 'question'")?,
+                    comparator: comparator.ok_or("resolve mode expects a 'comparator'")?,
+                    threshold: threshold.ok_or("resolve mode expects a 'threshold'")?,
+                    settlement_time: at.ok_or("resolve mode expects an 'at' unix timestamp")?,
+                },
+                tolerance,
+            },
+            "resolve" => return Err("resolve expects a single asset".to_string()),
+            "volatility" => Mode::Volatility { window: window.unwrap_or(Window::SevenDays) },
+            "stats" => Mode::Stats { fields: fields.unwrap_or_else(|| StatField::ALL.to_vec()) },
+            other => return Err(format!("Unknown mode '{}'", other)),
+        };
+
+        if convert != USD && !matches!(mode, Mode::Spot | Mode::AggregatorV3) {
+            return Err("convert is only supported by the spot and aggregator_v3 modes".to_string());
+        }
+
+        Ok(Self { assets, mode, convert })
+    }
+}
+
+/// Parses a comma separated list of assets, e.g. `1,ETH:1027,tether`.
+fn parse_assets(input: &str) -> Result<Vec<AssetQuery>, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("Empty input".to_string());
+    }
+
+    input.split(',').map(|asset| parse_asset(asset.trim())).collect()
+}
+
+fn parse_asset(asset: &str) -> Result<AssetQuery, String> {
+    if let Ok(id) = asset.parse::<u64>() {
+        return Ok(AssetQuery::Id(id));
+    }
+
+    let (name, id) = match asset.split_once(':') {
+        Some((name, id)) => (
+            name.trim(),
+            Some(id.trim().parse().map_err(|_| format!("Invalid asset id: '{}'", id))?),
+        ),
+        None => (asset, None),
+    };
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(format!("Invalid asset: '{}'", asset));
+    }
+    Ok(AssetQuery::Name { name: name.to_string(), id })
+}
+
+/// Parses a comma separated list of statistics, e.g. `market_cap,volume_24h`.
+fn parse_fields(value: &str) -> Result<Vec<StatField>, String> {
+    let mut fields = value
+        .split(',')
+        .map(|field| field.trim().parse::<StatField>())
+        .collect::<Result<Vec<_>, _>>()?;
+    fields.sort();
+    fields.dedup();
+    Ok(fields)
+}
+
+fn parse_currency(value: &str) -> Result<String, String> {
+    let currency = value.trim().to_uppercase();
+    if currency.is_empty() || !currency.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(format!("Invalid convert: '{}'", value));
+    }
+    Ok(currency)
+}
+
+fn parse_number(key: &str, value: &str) -> Result<u64, String> {
+    value.trim().parse().map_err(|_| format!("Invalid {}: '{}'", key.trim(), value))
+}
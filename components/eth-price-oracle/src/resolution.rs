@@ -0,0 +1,125 @@
+//! Resolution of binary price markets such as "Will ETH close above $4,000 on date X?".
+use crate::historical::get_price_at;
+use crate::trigger::scale_price;
+use serde::{Deserialize, Serialize};
+use wavs_wasi_chain::ethereum::alloy_primitives::B256;
+
+/// Payouts reported when the condition holds, outcome slots being `[YES, NO]`.
+const YES_PAYOUTS: [u64; 2] = [1, 0];
+const NO_PAYOUTS: [u64; 2] = [0, 1];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Comparator {
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+impl Comparator {
+    fn holds<T: PartialOrd>(&self, value: T, threshold: T) -> bool {
+        match self {
+            Comparator::Gt => value > threshold,
+            Comparator::Gte => value >= threshold,
+            Comparator::Lt => value < threshold,
+            Comparator::Lte => value <= threshold,
+        }
+    }
+}
+
+impl std::str::FromStr for Comparator {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "gt" => Ok(Comparator::Gt),
+            "gte" => Ok(Comparator::Gte),
+            "lt" => Ok(Comparator::Lt),
+            "lte" => Ok(Comparator::Lte),
+            _ => Err(format!("Invalid comparator '{}', expected gt, gte, lt or lte", s)),
+        }
+    }
+}
+
+/// A threshold market to settle.
+#[derive(Debug, Clone)]
+pub struct ThresholdMarket {
+    pub question_id: B256,
+    pub comparator: Comparator,
+    pub threshold: f64,
+    /// Unix timestamp (seconds) the market settles at
+    pub settlement_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MarketResolution {
+    pub question_id: B256,
+    /// Unix timestamp (seconds) the market settles at
+    pub settlement_time: u64,
+    #[serde(flatten)]
+    pub status: ResolutionStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ResolutionStatus {
+    Resolved {
+        /// CoinMarketCap id the request resolved to
+        id: u64,
+        symbol: String,
+        /// Price in USD at settlement
+        price: f64,
+        comparator: Comparator,
+        threshold: f64,
+        /// Unix timestamp (seconds) of the price sample used
+        sample_timestamp: u64,
+        /// Payout numerators for the `[YES, NO]` outcome slots, as passed to `reportPayouts`
+        payouts: Vec<u64>,
+    },
+    /// The settlement time hasn't come yet
+    NotReady {
+        reason: String,
+        /// Unix timestamp (seconds) after which the market should be triggered again
+        retry_after: u64,
+    },
+}
+
+/// Compares the price at settlement with the threshold. Both are scaled to `decimals` first so
+/// the comparison is exact at the precision submitted on chain. Markets triggered before their
+/// settlement time are reported as not ready, to be triggered again once it has passed.
+pub async fn resolve_threshold_market(
+    id: u64,
+    market: &ThresholdMarket,
+    tolerance_secs: u64,
+    decimals: u8,
+    now: u64,
+) -> Result<MarketResolution, String> {
+    let resolution = |status| MarketResolution {
+        question_id: market.question_id,
+        settlement_time: market.settlement_time,
+        status,
+    };
+    if now < market.settlement_time {
+        return Ok(resolution(ResolutionStatus::NotReady {
+            reason: format!("Market settles at {}", market.settlement_time),
+            retry_after: market.settlement_time,
+        }));
+    }
+
+    let settlement = get_price_at(id, market.settlement_time, tolerance_secs).await?;
+
+    let price = scale_price(settlement.price, decimals).map_err(|e| e.to_string())?;
+    let threshold = scale_price(market.threshold, decimals).map_err(|e| e.to_string())?;
+    let payouts = if market.comparator.holds(price, threshold) { YES_PAYOUTS } else { NO_PAYOUTS };
+
+    Ok(resolution(ResolutionStatus::Resolved {
+        id: settlement.id,
+        symbol: settlement.symbol,
+        price: settlement.price,
+        comparator: market.comparator,
+        threshold: market.threshold,
+        sample_timestamp: settlement.sample_timestamp,
+        payouts: payouts.to_vec(),
+    }))
+}
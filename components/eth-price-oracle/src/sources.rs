@@ -0,0 +1,143 @@
+//! Price sources queried by the oracle. Each adapter quotes the USD price of an [`Asset`].
+mod binance;
+mod coingecko;
+pub mod coinmarketcap;
+mod dex;
+mod kraken;
+
+pub use binance::Binance;
+pub use coingecko::CoinGecko;
+pub use coinmarketcap::CoinMarketCap;
+pub use dex::{Dex, DexPool};
+pub use kraken::Kraken;
+
+use crate::config::Config;
+use crate::request::AssetQuery;
+
+use futures::future::{join_all, LocalBoxFuture};
+
+/// Sources queried when `WAVS_ENV_PRICE_SOURCES` is unset. `dex` must be enabled explicitly
+/// along with `WAVS_ENV_DEX_POOLS`.
+pub const DEFAULT_SOURCES: [&str; 4] = ["coinmarketcap", "coingecko", "binance", "kraken"];
+
+/// Currency every source can quote in, and through which cross rates are derived.
+pub const USD: &str = "USD";
+/// Fiat currencies accepted by `convert`, with their CoinMarketCap ids. Anything else is looked
+/// up as a CoinMarketCap asset.
+const FIAT_CURRENCIES: [(&str, u64); 7] = [
+    ("USD", 2781),
+    ("EUR", 2790),
+    ("GBP", 2791),
+    ("JPY", 2797),
+    ("CHF", 2785),
+    ("CAD", 2784),
+    ("AUD", 2782),
+];
+
+/// An asset as identified by CoinMarketCap, shared by all sources to build their requests.
+#[derive(Debug, Clone)]
+pub struct Asset {
+    pub id: u64,
+    pub symbol: String,
+    pub slug: String,
+}
+
+impl Asset {
+    /// Whether the asset is one of the fiat currencies accepted by `convert`
+    pub fn is_fiat(&self) -> bool {
+        FIAT_CURRENCIES.iter().any(|(_, id)| *id == self.id)
+    }
+}
+
+/// A single price observation from one source.
+#[derive(Debug, Clone)]
+pub struct Quote {
+    pub source: &'static str,
+    pub price: f64,
+    /// Unix timestamp (seconds) of the observation
+    pub timestamp: u64,
+}
+
+/// A provider able to quote the price of an [`Asset`].
+pub trait PriceSource {
+    /// Identifier used in logs and in `WAVS_ENV_PRICE_SOURCES`
+    fn name(&self) -> &'static str;
+
+    /// Whether the source can quote fiat currencies as assets, e.g. EUR in USD. Exchanges can,
+    /// through their fiat pairs, while crypto price APIs only list crypto assets.
+    fn quotes_fiat(&self) -> bool {
+        true
+    }
+
+    /// Quotes `asset` in `currency`, an upper case code such as `USD`, `EUR` or `BTC`. Sources
+    /// without a direct market for the pair fail.
+    fn fetch_quote<'a>(
+        &'a self,
+        asset: &'a Asset,
+        currency: &'a str,
+    ) -> LocalBoxFuture<'a, Result<Quote, String>>;
+}
+
+/// Builds the sources enabled by the operator, e.g. `["coingecko", "kraken"]`.
+pub fn from_config(config: &Config) -> Result<Vec<Box<dyn PriceSource>>, String> {
+    config
+        .price_sources
+        .iter()
+        .map(|name| -> Result<Box<dyn PriceSource>, String> {
+            match name.as_str() {
+                "coinmarketcap" => Ok(Box::new(CoinMarketCap)),
+                "coingecko" => Ok(Box::new(CoinGecko::new(config.coingecko_ids.clone()))),
+                "binance" => Ok(Box::new(Binance)),
+                "kraken" => Ok(Box::new(Kraken)),
+                "dex" => Ok(Box::new(Dex {
+                    chain: config.dex_chain.clone(),
+                    pools: config.dex_pools.clone(),
+                })),
+                _ => Err(format!("Unknown price source: '{}'", name)),
+            }
+        })
+        .collect()
+}
+
+/// Queries every source concurrently. Failing sources are logged and left out, as are sources
+/// that can't quote a fiat `asset`.
+pub async fn fetch_quotes(
+    sources: &[Box<dyn PriceSource>],
+    asset: &Asset,
+    currency: &str,
+) -> Vec<Quote> {
+    let sources: Vec<_> =
+        sources.iter().filter(|source| !asset.is_fiat() || source.quotes_fiat()).collect();
+    let results = join_all(sources.iter().map(|source| source.fetch_quote(asset, currency))).await;
+
+    sources
+        .iter()
+        .zip(results)
+        .filter_map(|(source, result)| match result {
+            Ok(quote) => Some(quote),
+            Err(e) => {
+                eprintln!("{} failed to quote {}/{}: {}", source.name(), asset.symbol, currency, e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// The quote currency as an [`Asset`], so its USD price can be fetched for cross rates.
+pub async fn currency_asset(currency: &str) -> Result<Asset, String> {
+    if let Some((symbol, id)) = FIAT_CURRENCIES.iter().find(|(symbol, _)| *symbol == currency) {
+        return Ok(Asset { id: *id, symbol: symbol.to_string(), slug: symbol.to_lowercase() });
+    }
+
+    let query = AssetQuery::Name { name: currency.to_string(), id: None };
+    let id = coinmarketcap::resolve_ids(&[query]).await?[0];
+    Ok(coinmarketcap::fetch_asset(id).await?)
+}
+
+/// Current unix timestamp in seconds, for sources that don't report one.
+pub fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
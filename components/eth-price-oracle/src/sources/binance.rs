@@ -0,0 +1,50 @@
+use super::{Asset, PriceSource, Quote, USD};
+use futures::future::LocalBoxFuture;
+use serde::Deserialize;
+use wavs_wasi_chain::http::{fetch_json, http_request_get};
+use wstd::http::HeaderValue;
+
+/// <https://developers.binance.com/docs/binance-spot-api-docs/rest-api/market-data-endpoints>,
+/// quoting the USDT pair as USD.
+pub struct Binance;
+
+#[derive(Debug, Deserialize)]
+struct Ticker24h {
+    #[serde(rename = "lastPrice")]
+    last_price: String,
+    /// Milliseconds since the unix epoch
+    #[serde(rename = "closeTime")]
+    close_time: u64,
+}
+
+impl PriceSource for Binance {
+    fn name(&self) -> &'static str {
+        "binance"
+    }
+
+    fn fetch_quote<'a>(
+        &'a self,
+        asset: &'a Asset,
+        currency: &'a str,
+    ) -> LocalBoxFuture<'a, Result<Quote, String>> {
+        Box::pin(async move {
+            let quote = if currency == USD { "USDT" } else { currency };
+            let url = format!(
+                "https://api.binance.com/api/v3/ticker/24hr?symbol={}{}",
+                asset.symbol.to_uppercase(),
+                quote
+            );
+
+            let mut req = http_request_get(&url).map_err(|e| e.to_string())?;
+            req.headers_mut().insert("Accept", HeaderValue::from_static("application/json"));
+
+            let ticker: Ticker24h = fetch_json(req).await.map_err(|e| e.to_string())?;
+
+            Ok(Quote {
+                source: self.name(),
+                price: ticker.last_price.parse().map_err(|_| "Invalid lastPrice")?,
+                timestamp: ticker.close_time / 1000,
+            })
+        })
+    }
+}
@@ -0,0 +1,122 @@
+use super::{Asset, PriceSource, Quote};
+use futures::future::{LocalBoxFuture, Shared};
+use futures::FutureExt;
+use serde::Deserialize;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use wavs_wasi_chain::http::{fetch_json, http_request_get};
+use wstd::http::HeaderValue;
+
+/// <https://docs.coingecko.com/reference/simple-price>. Quotes fiat currencies and the major
+/// crypto assets directly.
+///
+/// CoinGecko ids differ from CoinMarketCap slugs (e.g. `binancecoin` for `bnb`), so assets are
+/// looked up in `ids`, configured with `WAVS_ENV_COINGECKO_IDS`, then matched in the CoinGecko
+/// coin list on their symbol and name.
+pub struct CoinGecko {
+    ids: HashMap<u64, String>,
+    /// The coin list, fetched at most once per run and shared by every asset quoted, including
+    /// the currencies of cross rates
+    coins: RefCell<Option<CoinList>>,
+}
+
+type CoinList = Shared<LocalBoxFuture<'static, Result<Rc<Vec<Coin>>, String>>>;
+
+/// An entry of <https://docs.coingecko.com/reference/coins-list>
+#[derive(Debug, Deserialize)]
+struct Coin {
+    id: String,
+    symbol: String,
+    name: String,
+}
+
+impl CoinGecko {
+    pub fn new(ids: HashMap<u64, String>) -> Self {
+        Self { ids, coins: RefCell::new(None) }
+    }
+
+    /// The coin list, requested on first use. Assets quoted concurrently wait for the same request.
+    fn coins(&self) -> CoinList {
+        self.coins.borrow_mut().get_or_insert_with(|| fetch_coins().boxed_local().shared()).clone()
+    }
+
+    async fn coin_id(&self, asset: &Asset) -> Result<String, String> {
+        if let Some(id) = self.ids.get(&asset.id) {
+            return Ok(id.clone());
+        }
+
+        let coins = self.coins().await?;
+
+        // CoinMarketCap slugs are derived from the name, e.g. `avalanche` for Avalanche (AVAX)
+        let mut matches = coins.iter().filter(|coin| {
+            coin.symbol.eq_ignore_ascii_case(&asset.symbol) && slugify(&coin.name) == asset.slug
+        });
+        match (matches.next(), matches.next()) {
+            (Some(coin), None) => Ok(coin.id.clone()),
+            (None, _) => Err(format!("No coin matches {} ({})", asset.symbol, asset.slug)),
+            (Some(_), Some(_)) => Err(format!(
+                "Several coins match {} ({}), map CoinMarketCap id {} in WAVS_ENV_COINGECKO_IDS",
+                asset.symbol, asset.slug, asset.id
+            )),
+        }
+    }
+}
+
+async fn fetch_coins() -> Result<Rc<Vec<Coin>>, String> {
+    let mut req = http_request_get("https://api.coingecko.com/api/v3/coins/list")
+        .map_err(|e| e.to_string())?;
+    req.headers_mut().insert("Accept", HeaderValue::from_static("application/json"));
+    let coins: Vec<Coin> = fetch_json(req).await.map_err(|e| e.to_string())?;
+    Ok(Rc::new(coins))
+}
+
+/// Lower case name with runs of other characters replaced by a dash, as CoinMarketCap slugs are.
+fn slugify(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+impl PriceSource for CoinGecko {
+    fn name(&self) -> &'static str {
+        "coingecko"
+    }
+
+    fn quotes_fiat(&self) -> bool {
+        false
+    }
+
+    fn fetch_quote<'a>(
+        &'a self,
+        asset: &'a Asset,
+        currency: &'a str,
+    ) -> LocalBoxFuture<'a, Result<Quote, String>> {
+        Box::pin(async move {
+            let coin_id = self.coin_id(asset).await?;
+            let vs_currency = currency.to_lowercase();
+            let url = format!(
+                "https://api.coingecko.com/api/v3/simple/price?ids={}&vs_currencies={}&include_last_updated_at=true",
+                coin_id, vs_currency
+            );
+
+            let mut req = http_request_get(&url).map_err(|e| e.to_string())?;
+            req.headers_mut().insert("Accept", HeaderValue::from_static("application/json"));
+
+            // e.g. {"ethereum":{"usd":2650.12,"last_updated_at":1738182114}}
+            let json: HashMap<String, HashMap<String, f64>> =
+                fetch_json(req).await.map_err(|e| e.to_string())?;
+            let fields = json.get(&coin_id).ok_or("Asset not listed")?;
+
+            Ok(Quote {
+                source: self.name(),
+                price: *fields
+                    .get(&vs_currency)
+                    .ok_or_else(|| format!("Missing {} price", vs_currency))?,
+                timestamp: fields.get("last_updated_at").copied().unwrap_or_default() as u64,
+            })
+        })
+    }
+}
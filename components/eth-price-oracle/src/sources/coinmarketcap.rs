@@ -0,0 +1,417 @@
+//! CoinMarketCap, through the Pro API when the operator sets `WAVS_ENV_CMC_API_KEY`. The public
+//! data-api behind coinmarketcap.com isn't a documented API, so it is only queried when the
+//! operator opts in with `WAVS_ENV_CMC_DATA_API=true`.
+mod pro;
+
+use super::{Asset, PriceSource, Quote, USD};
+use crate::request::AssetQuery;
+use crate::trigger::parse_timestamp;
+use futures::future::LocalBoxFuture;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use wavs_wasi_chain::http::{fetch_json, http_request_get};
+use wstd::http::HeaderValue;
+
+/// Errors reported by CoinMarketCap in `status.error_code`/`status.error_message`, or met while
+/// reaching it.
+#[derive(Debug, thiserror::Error)]
+pub enum CmcError {
+    #[error("CoinMarketCap API key rejected ({code}): {message}")]
+    Unauthorized { code: i64, message: String },
+    #[error("CoinMarketCap plan does not cover this request ({code}): {message}")]
+    PlanNotAuthorized { code: i64, message: String },
+    #[error("CoinMarketCap rate limit reached ({code}): {message}")]
+    RateLimited { code: i64, message: String },
+    #[error("CoinMarketCap rejected the request: {0}")]
+    BadRequest(String),
+    #[error("CoinMarketCap error {code}: {message}")]
+    Api { code: i64, message: String },
+    #[error("CoinMarketCap has no data for {0}")]
+    NotFound(String),
+    #[error("CoinMarketCap request failed: {0}")]
+    Http(String),
+    #[error("CoinMarketCap {0} needs the public data-api, enable it with {DATA_API_ENV}=true")]
+    DataApiDisabled(&'static str),
+}
+
+/// Opts into the public data-api, the only source of charts and the fallback without an API key
+const DATA_API_ENV: &str = "WAVS_ENV_CMC_DATA_API";
+
+thread_local! {
+    /// USD market data fetched during this run, keyed by id. Resolving an asset and quoting it
+    /// in USD read the same data, so CoinMarketCap is only asked once per asset. Every trigger
+    /// runs in a fresh component instance, so nothing outlives the run.
+    static USD_MARKETS: RefCell<HashMap<u64, MarketQuote>> = RefCell::new(HashMap::new());
+}
+
+impl From<CmcError> for String {
+    fn from(e: CmcError) -> Self {
+        e.to_string()
+    }
+}
+
+/// Latest market data of an asset, from whichever API is in use.
+#[derive(Debug, Clone)]
+pub struct MarketQuote {
+    pub symbol: String,
+    pub slug: String,
+    pub price: f64,
+    /// Unix timestamp (seconds) of the quote
+    pub timestamp: u64,
+    pub market_cap: f64,
+    pub volume_24h: f64,
+    pub circulating_supply: f64,
+    pub total_supply: f64,
+    /// Price change over the last 24 hours, in percent
+    pub change_24h: f64,
+}
+
+/// CoinMarketCap quotes, also used to resolve the [`Asset`] behind an id.
+pub struct CoinMarketCap;
+
+impl PriceSource for CoinMarketCap {
+    fn name(&self) -> &'static str {
+        "coinmarketcap"
+    }
+
+    fn quotes_fiat(&self) -> bool {
+        false
+    }
+
+    fn fetch_quote<'a>(
+        &'a self,
+        asset: &'a Asset,
+        currency: &'a str,
+    ) -> LocalBoxFuture<'a, Result<Quote, String>> {
+        Box::pin(async move {
+            let market = fetch_market(asset.id, currency).await?;
+            Ok(Quote { source: self.name(), price: market.price, timestamp: market.timestamp })
+        })
+    }
+}
+
+/// Fetches the latest market data of an asset in `currency`. Only the Pro API converts to other
+/// currencies than USD, the data-api is used without an API key when enabled. USD data is
+/// fetched once per asset and run.
+pub async fn fetch_market(id: u64, currency: &str) -> Result<MarketQuote, CmcError> {
+    if currency != USD {
+        return fetch_market_uncached(id, currency).await;
+    }
+    if let Some(market) = USD_MARKETS.with(|markets| markets.borrow().get(&id).cloned()) {
+        return Ok(market);
+    }
+    let market = fetch_market_uncached(id, USD).await?;
+    USD_MARKETS.with(|markets| markets.borrow_mut().insert(id, market.clone()));
+    Ok(market)
+}
+
+async fn fetch_market_uncached(id: u64, currency: &str) -> Result<MarketQuote, CmcError> {
+    if let Some(client) = pro::Client::from_env() {
+        return client.quote(id, currency).await;
+    }
+    if currency != USD {
+        return Err(CmcError::BadRequest(format!(
+            "the public data-api only quotes {}, set WAVS_ENV_CMC_API_KEY",
+            USD
+        )));
+    }
+
+    let url = format!(
+        "https://api.coinmarketcap.com/data-api/v3/cryptocurrency/detail?id={}&range=1h",
+        id
+    );
+    let json: Root = fetch_data_api(&url, "quotes without WAVS_ENV_CMC_API_KEY").await?;
+    json.status.check()?;
+    let data = json.data.ok_or_else(|| CmcError::NotFound(id.to_string()))?;
+
+    Ok(MarketQuote {
+        symbol: data.symbol,
+        slug: data.slug,
+        price: data.statistics.price,
+        timestamp: parse_timestamp(&json.status.timestamp)
+            .map_err(|e| CmcError::Http(e.to_string()))?,
+        market_cap: data.statistics.market_cap,
+        volume_24h: data.volume,
+        circulating_supply: data.statistics.circulating_supply,
+        total_supply: data.statistics.total_supply,
+        change_24h: data.statistics.price_change_percentage_24h,
+    })
+}
+
+/// Looks up the symbol and slug of a CoinMarketCap id.
+pub async fn fetch_asset(id: u64) -> Result<Asset, CmcError> {
+    let market = fetch_market(id, USD).await?;
+    Ok(Asset { id, symbol: market.symbol, slug: market.slug })
+}
+
+/// Resolves the CoinMarketCap id of each asset. The CoinMarketCap map is only fetched when some
+/// assets are given by symbol or slug.
+pub async fn resolve_ids(assets: &[AssetQuery]) -> Result<Vec<u64>, String> {
+    let listings = if assets.iter().all(|asset| matches!(asset, AssetQuery::Id(_))) {
+        Vec::new()
+    } else {
+        fetch_map().await?
+    };
+
+    assets
+        .iter()
+        .map(|asset| match asset {
+            AssetQuery::Id(id) => Ok(*id),
+            AssetQuery::Name { name, id } => select_listing(&listings, name, *id),
+        })
+        .collect()
+}
+
+/// Picks the listing for a symbol or slug. Symbols aren't unique and some slugs are other
+/// tokens' symbols, e.g. `uni` and `sol`, so symbols come first and the best ranked listing with
+/// the symbol is used. A slug is only matched when no listing has the symbol. An explicit `id`
+/// must be one of the listings with that symbol or slug.
+fn select_listing(listings: &[Listing], name: &str, id: Option<u64>) -> Result<u64, String> {
+    if let Some(id) = id {
+        return listings
+            .iter()
+            .find(|listing| {
+                listing.id == id
+                    && (listing.symbol.eq_ignore_ascii_case(name)
+                        || listing.slug.eq_ignore_ascii_case(name))
+            })
+            .map(|listing| listing.id)
+            .ok_or_else(|| format!("CoinMarketCap id {} is not listed as '{}'", id, name));
+    }
+
+    let mut candidates: Vec<&Listing> =
+        listings.iter().filter(|listing| listing.symbol.eq_ignore_ascii_case(name)).collect();
+
+    // Unranked listings come last
+    candidates.sort_by_key(|listing| listing.rank.unwrap_or(u64::MAX));
+    let Some(best) = candidates.first() else {
+        return listings
+            .iter()
+            .find(|listing| listing.slug.eq_ignore_ascii_case(name))
+            .map(|listing| listing.id)
+            .ok_or_else(|| format!("Unknown asset: '{}'", name));
+    };
+    if candidates.len() > 1 {
+        eprintln!(
+            "'{}' matches {} listings, using {} ({}). Pin one with {}:ID",
+            name,
+            candidates.len(),
+            best.id,
+            best.slug,
+            name
+        );
+    }
+    Ok(best.id)
+}
+
+async fn fetch_map() -> Result<Vec<Listing>, CmcError> {
+    if let Some(client) = pro::Client::from_env() {
+        return client.map().await;
+    }
+
+    let url = "https://api.coinmarketcap.com/data-api/v3/map/all?listing_status=active&cryptoAux=is_active,status&start=1&limit=10000";
+    let json: MapRoot = fetch_data_api(url, "listings without WAVS_ENV_CMC_API_KEY").await?;
+    json.status.check()?;
+    Ok(json.data.map(|data| data.crypto_currency_map).unwrap_or_default())
+}
+
+/// Fetches the historical `(timestamp, price)` points of an asset, sorted by time.
+///
+/// `range` is one of the ranges offered by the CoinMarketCap charts (e.g. `1D`, `7D`, `1M`).
+/// Historical data needs a paid Pro plan, so charts always come from the public data-api, which
+/// must be enabled.
+pub async fn fetch_chart(id: u64, range: &str) -> Result<Vec<(u64, f64)>, CmcError> {
+    let url = format!(
+        "https://api.coinmarketcap.com/data-api/v3/cryptocurrency/detail/chart?id={}&range={}",
+        id, range
+    );
+    let json: ChartRoot = fetch_data_api(&url, "charts").await?;
+    json.status.check()?;
+
+    let mut points = json
+        .data
+        .ok_or_else(|| CmcError::NotFound(id.to_string()))?
+        .points
+        .into_iter()
+        .filter_map(|(timestamp, point)| Some((timestamp.parse().ok()?, *point.v.first()?)))
+        .collect::<Vec<(u64, f64)>>();
+    points.sort_by_key(|(timestamp, _)| *timestamp);
+    Ok(points)
+}
+
+/// Whether the operator enabled the public data-api with [`DATA_API_ENV`].
+fn data_api_enabled() -> Result<bool, CmcError> {
+    match std::env::var(DATA_API_ENV) {
+        Ok(value) => value.trim().parse().map_err(|_| {
+            CmcError::BadRequest(format!("Invalid value for {}: '{}'", DATA_API_ENV, value))
+        }),
+        Err(_) => Ok(false),
+    }
+}
+
+/// Fetches `url` from the public data-api, failing when it isn't enabled. `purpose` tells the
+/// operator what needed it.
+async fn fetch_data_api<T: serde::de::DeserializeOwned>(
+    url: &str,
+    purpose: &'static str,
+) -> Result<T, CmcError> {
+    if !data_api_enabled()? {
+        return Err(CmcError::DataApiDisabled(purpose));
+    }
+    let current_time = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+
+    let mut req = http_request_get(url).map_err(|e| CmcError::Http(e.to_string()))?;
+    req.headers_mut().insert("Accept", HeaderValue::from_static("application/json"));
+    req.headers_mut().insert("Content-Type", HeaderValue::from_static("application/json"));
+    req.headers_mut()
+        .insert("User-Agent", HeaderValue::from_static("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/132.0.0.0 Safari/537.36"));
+    req.headers_mut().insert(
+        "Cookie",
+        HeaderValue::from_str(&format!("myrandom_cookie={}", current_time))
+            .map_err(|e| CmcError::Http(e.to_string()))?,
+    );
+
+    fetch_json(req).await.map_err(|e| CmcError::Http(e.to_string()))
+}
+
+/// -----
+/// <https://transform.tools/json-to-rust-serde>
+/// Generated from <https://api.coinmarketcap.com/data-api/v3/cryptocurrency/detail?id=1&range=1h>
+/// -----
+///
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Root {
+    /// Missing when `status` reports an error
+    pub data: Option<Data>,
+    pub status: Status,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Data {
+    pub id: f64,
+    pub name: String,
+    pub symbol: String,
+    pub statistics: Statistics,
+    /// Traded volume over the last 24 hours in USD
+    #[serde(default)]
+    pub volume: f64,
+    pub description: String,
+    pub category: String,
+    pub slug: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Statistics {
+    pub price: f64,
+    #[serde(rename = "priceChangePercentage24h", default)]
+    pub price_change_percentage_24h: f64,
+    #[serde(rename = "marketCap", default)]
+    pub market_cap: f64,
+    #[serde(rename = "circulatingSupply", default)]
+    pub circulating_supply: f64,
+    #[serde(rename = "totalSupply")]
+    pub total_supply: f64,
+}
+
+/// Shared by the data-api and the Pro API, which reports `error_code` as a number and a null
+/// `error_message` on success.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Status {
+    pub timestamp: String,
+    pub error_code: ErrorCode,
+    pub error_message: Option<String>,
+}
+
+impl Status {
+    /// Maps an error reported in the status to its [`CmcError`].
+    ///
+    /// <https://coinmarketcap.com/api/documentation/v1/#section/Errors-and-Rate-Limits>
+    pub fn check(&self) -> Result<(), CmcError> {
+        let code = self.error_code.code();
+        let message = self.error_message.clone().unwrap_or_default();
+        match code {
+            0 => Ok(()),
+            400 => Err(CmcError::BadRequest(message)),
+            1001 | 1002 | 1005 | 1007 => Err(CmcError::Unauthorized { code, message }),
+            1003 | 1004 | 1006 => Err(CmcError::PlanNotAuthorized { code, message }),
+            1008..=1011 => Err(CmcError::RateLimited { code, message }),
+            _ => Err(CmcError::Api { code, message }),
+        }
+    }
+}
+
+/// A number in the Pro API, a string such as `"0"` in the data-api.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ErrorCode {
+    Number(i64),
+    Text(String),
+}
+
+impl Default for ErrorCode {
+    fn default() -> Self {
+        ErrorCode::Number(0)
+    }
+}
+
+impl ErrorCode {
+    pub fn code(&self) -> i64 {
+        match self {
+            ErrorCode::Number(code) => *code,
+            ErrorCode::Text(code) => code.parse().unwrap_or(-1),
+        }
+    }
+}
+
+/// -----
+/// Generated from <https://api.coinmarketcap.com/data-api/v3/cryptocurrency/detail/chart?id=1&range=1D>
+/// -----
+///
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChartRoot {
+    pub data: Option<ChartData>,
+    pub status: Status,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChartData {
+    /// Keyed by unix timestamp (seconds)
+    pub points: BTreeMap<String, ChartPoint>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChartPoint {
+    /// `[price, volume, market cap, ...]` in USD
+    pub v: Vec<f64>,
+}
+
+/// -----
+/// Generated from <https://api.coinmarketcap.com/data-api/v3/map/all?listing_status=active>
+/// -----
+///
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapRoot {
+    pub data: Option<MapData>,
+    pub status: Status,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapData {
+    #[serde(rename = "cryptoCurrencyMap")]
+    pub crypto_currency_map: Vec<Listing>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Listing {
+    pub id: u64,
+    pub name: String,
+    pub symbol: String,
+    pub slug: String,
+    /// Market cap rank, missing for untracked listings
+    #[serde(default)]
+    pub rank: Option<u64>,
+}
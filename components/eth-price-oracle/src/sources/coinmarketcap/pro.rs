@@ -0,0 +1,104 @@
+use super::{CmcError, Listing, MarketQuote, Status};
+use crate::trigger::parse_timestamp;
+use serde::Deserialize;
+use std::collections::HashMap;
+use wavs_wasi_chain::http::{fetch_json, http_request_get};
+use wstd::http::HeaderValue;
+
+const BASE_URL: &str = "https://pro-api.coinmarketcap.com";
+const API_KEY_ENV: &str = "WAVS_ENV_CMC_API_KEY";
+
+/// <https://coinmarketcap.com/api/documentation/v1/>, authenticated with the operator's key.
+pub struct Client {
+    api_key: HeaderValue,
+}
+
+impl Client {
+    /// The client for `WAVS_ENV_CMC_API_KEY`, `None` when the operator didn't set a key.
+    pub fn from_env() -> Option<Self> {
+        let api_key = std::env::var(API_KEY_ENV).ok()?;
+        let api_key = HeaderValue::from_str(api_key.trim()).ok().filter(|key| !key.is_empty())?;
+        Some(Self { api_key })
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T, CmcError> {
+        let url = format!("{}{}", BASE_URL, path);
+        let mut req = http_request_get(&url).map_err(|e| CmcError::Http(e.to_string()))?;
+        req.headers_mut().insert("Accept", HeaderValue::from_static("application/json"));
+        req.headers_mut().insert("X-CMC_PRO_API_KEY", self.api_key.clone());
+
+        fetch_json(req).await.map_err(|e| CmcError::Http(e.to_string()))
+    }
+
+    /// `/v2/cryptocurrency/quotes/latest` for a single id, converted to `currency`.
+    pub async fn quote(&self, id: u64, currency: &str) -> Result<MarketQuote, CmcError> {
+        let path = format!("/v2/cryptocurrency/quotes/latest?id={}&convert={}", id, currency);
+        let json: QuotesRoot = self.get(&path).await?;
+        json.status.check()?;
+
+        let asset = json
+            .data
+            .and_then(|mut data| data.remove(&id.to_string()))
+            .ok_or_else(|| CmcError::NotFound(id.to_string()))?;
+        let quote = asset
+            .quote
+            .get(currency)
+            .ok_or_else(|| CmcError::NotFound(format!("{} in {}", id, currency)))?;
+
+        Ok(MarketQuote {
+            symbol: asset.symbol,
+            slug: asset.slug,
+            price: quote.price.ok_or_else(|| CmcError::NotFound(format!("{} price", id)))?,
+            timestamp: parse_timestamp(&quote.last_updated)
+                .map_err(|e| CmcError::Http(e.to_string()))?,
+            market_cap: quote.market_cap.unwrap_or_default(),
+            volume_24h: quote.volume_24h.unwrap_or_default(),
+            circulating_supply: asset.circulating_supply.unwrap_or_default(),
+            total_supply: asset.total_supply.unwrap_or_default(),
+            change_24h: quote.percent_change_24h.unwrap_or_default(),
+        })
+    }
+
+    /// `/v1/cryptocurrency/map` of the active listings.
+    pub async fn map(&self) -> Result<Vec<Listing>, CmcError> {
+        let json: MapRoot = self.get("/v1/cryptocurrency/map?listing_status=active").await?;
+        json.status.check()?;
+        Ok(json.data.unwrap_or_default())
+    }
+}
+
+/// -----
+/// Generated from <https://pro-api.coinmarketcap.com/v2/cryptocurrency/quotes/latest?id=1027>
+/// -----
+///
+#[derive(Debug, Deserialize)]
+struct QuotesRoot {
+    /// Keyed by id, missing when `status` reports an error
+    data: Option<HashMap<String, ProAsset>>,
+    status: Status,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProAsset {
+    symbol: String,
+    slug: String,
+    circulating_supply: Option<f64>,
+    total_supply: Option<f64>,
+    /// Keyed by the `convert` currency
+    quote: HashMap<String, ProQuote>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProQuote {
+    price: Option<f64>,
+    volume_24h: Option<f64>,
+    percent_change_24h: Option<f64>,
+    market_cap: Option<f64>,
+    last_updated: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MapRoot {
+    data: Option<Vec<Listing>>,
+    status: Status,
+}
@@ -0,0 +1,164 @@
+use super::{unix_now, Asset, PriceSource, Quote, USD};
+use crate::eth::{eth_call, http_endpoint};
+use alloy_sol_macro::sol;
+use alloy_sol_types::SolCall;
+use futures::future::{try_join, LocalBoxFuture};
+use std::str::FromStr;
+use wavs_wasi_chain::ethereum::alloy_primitives::Address;
+
+sol! {
+    interface IPool {
+        function token0() external view returns (address);
+        function token1() external view returns (address);
+    }
+
+    interface IUniswapV2Pair {
+        function getReserves() external view returns (uint112 reserve0, uint112 reserve1, uint32 blockTimestampLast);
+    }
+
+    interface IUniswapV3Pool {
+        function slot0() external view returns (uint160 sqrtPriceX96, int24 tick, uint16 observationIndex, uint16 observationCardinality, uint16 observationCardinalityNext, uint8 feeProtocol, bool unlocked);
+    }
+
+    interface IERC20Metadata {
+        function decimals() external view returns (uint8);
+    }
+}
+
+/// Spot price read from Uniswap V2 pairs and V3 pools, quoted in the pool's other token which is
+/// assumed to be a USD stablecoin.
+pub struct Dex {
+    pub chain: String,
+    pub pools: Vec<DexPool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PoolVersion {
+    V2,
+    V3,
+}
+
+/// A pool from `WAVS_ENV_DEX_POOLS`, written `SYMBOL=VERSION:ADDRESS[:token0|token1]`, e.g.
+/// `ETH=v3:0x88e6a0c2ddd26feeb64f039a2c41296fcb3f5640:token1` for the USDC/WETH pool.
+#[derive(Debug, Clone)]
+pub struct DexPool {
+    pub symbol: String,
+    pub version: PoolVersion,
+    pub address: Address,
+    /// Whether the asset is `token1` of the pool, `token0` otherwise
+    pub base_is_token1: bool,
+}
+
+impl FromStr for DexPool {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            || format!("Invalid pool '{}', expected SYMBOL=v2|v3:ADDRESS[:token0|token1]", s);
+
+        let (symbol, pool) = s.split_once('=').ok_or_else(invalid)?;
+        let mut parts = pool.split(':').map(str::trim);
+        let version = match parts.next() {
+            Some("v2") => PoolVersion::V2,
+            Some("v3") => PoolVersion::V3,
+            _ => return Err(invalid()),
+        };
+        let address = parts.next().and_then(|address| address.parse().ok()).ok_or_else(invalid)?;
+        let base_is_token1 = match parts.next() {
+            None | Some("token0") => false,
+            Some("token1") => true,
+            Some(_) => return Err(invalid()),
+        };
+        if parts.next().is_some() || symbol.trim().is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(DexPool { symbol: symbol.trim().to_uppercase(), version, address, base_is_token1 })
+    }
+}
+
+impl PriceSource for Dex {
+    fn name(&self) -> &'static str {
+        "dex"
+    }
+
+    fn fetch_quote<'a>(
+        &'a self,
+        asset: &'a Asset,
+        currency: &'a str,
+    ) -> LocalBoxFuture<'a, Result<Quote, String>> {
+        Box::pin(async move {
+            if currency != USD {
+                return Err(format!("Only quotes {}", USD));
+            }
+            let pool = self
+                .pools
+                .iter()
+                .find(|pool| pool.symbol.eq_ignore_ascii_case(&asset.symbol))
+                .ok_or_else(|| format!("No pool configured for {}", asset.symbol))?;
+            let endpoint = http_endpoint(&self.chain)?;
+
+            let (decimals0, decimals1) = try_join(
+                token_decimals(&endpoint, pool.address, false),
+                token_decimals(&endpoint, pool.address, true),
+            )
+            .await?;
+            let price0 = match pool.version {
+                PoolVersion::V2 => v2_price(&endpoint, pool.address, decimals0, decimals1).await?,
+                PoolVersion::V3 => v3_price(&endpoint, pool.address, decimals0, decimals1).await?,
+            };
+
+            let price = if pool.base_is_token1 { 1.0 / price0 } else { price0 };
+            if !price.is_finite() || price <= 0.0 {
+                return Err(format!("Pool {} has no liquidity", pool.address));
+            }
+
+            Ok(Quote { source: self.name(), price, timestamp: unix_now() })
+        })
+    }
+}
+
+/// Decimals of `token1` of the pool when `token1` is set, of `token0` otherwise.
+async fn token_decimals(endpoint: &str, pool: Address, token1: bool) -> Result<u8, String> {
+    let token = if token1 {
+        let data = eth_call(endpoint, pool, IPool::token1Call {}.abi_encode()).await?;
+        IPool::token1Call::abi_decode_returns(&data, true).map_err(|e| e.to_string())?._0
+    } else {
+        let data = eth_call(endpoint, pool, IPool::token0Call {}.abi_encode()).await?;
+        IPool::token0Call::abi_decode_returns(&data, true).map_err(|e| e.to_string())?._0
+    };
+
+    let data = eth_call(endpoint, token, IERC20Metadata::decimalsCall {}.abi_encode()).await?;
+    Ok(IERC20Metadata::decimalsCall::abi_decode_returns(&data, true).map_err(|e| e.to_string())?._0)
+}
+
+/// Price of token0 in token1 from the pair reserves.
+async fn v2_price(
+    endpoint: &str,
+    pair: Address,
+    decimals0: u8,
+    decimals1: u8,
+) -> Result<f64, String> {
+    let data = eth_call(endpoint, pair, IUniswapV2Pair::getReservesCall {}.abi_encode()).await?;
+    let reserves = IUniswapV2Pair::getReservesCall::abi_decode_returns(&data, true)
+        .map_err(|e| e.to_string())?;
+
+    let reserve0 = f64::from(reserves.reserve0) / 10f64.powi(decimals0 as i32);
+    let reserve1 = f64::from(reserves.reserve1) / 10f64.powi(decimals1 as i32);
+    Ok(reserve1 / reserve0)
+}
+
+/// Price of token0 in token1 from the pool's current `sqrtPriceX96`.
+async fn v3_price(
+    endpoint: &str,
+    pool: Address,
+    decimals0: u8,
+    decimals1: u8,
+) -> Result<f64, String> {
+    let data = eth_call(endpoint, pool, IUniswapV3Pool::slot0Call {}.abi_encode()).await?;
+    let slot0 =
+        IUniswapV3Pool::slot0Call::abi_decode_returns(&data, true).map_err(|e| e.to_string())?;
+
+    let sqrt_price = f64::from(slot0.sqrtPriceX96) / 2f64.powi(96);
+    Ok(sqrt_price * sqrt_price * 10f64.powi(decimals0 as i32 - decimals1 as i32))
+}
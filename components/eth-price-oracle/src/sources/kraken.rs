@@ -0,0 +1,69 @@
+use super::{unix_now, Asset, PriceSource, Quote};
+use futures::future::LocalBoxFuture;
+use serde::Deserialize;
+use std::collections::HashMap;
+use wavs_wasi_chain::http::{fetch_json, http_request_get};
+use wstd::http::HeaderValue;
+
+/// <https://docs.kraken.com/api/docs/rest-api/get-ticker-information>
+pub struct Kraken;
+
+#[derive(Debug, Deserialize)]
+struct TickerResponse {
+    error: Vec<String>,
+    #[serde(default)]
+    result: HashMap<String, Ticker>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Ticker {
+    /// Last trade closed: `[price, lot volume]`
+    c: Vec<String>,
+}
+
+impl PriceSource for Kraken {
+    fn name(&self) -> &'static str {
+        "kraken"
+    }
+
+    fn fetch_quote<'a>(
+        &'a self,
+        asset: &'a Asset,
+        currency: &'a str,
+    ) -> LocalBoxFuture<'a, Result<Quote, String>> {
+        Box::pin(async move {
+            let url = format!(
+                "https://api.kraken.com/0/public/Ticker?pair={}{}",
+                kraken_symbol(&asset.symbol),
+                kraken_symbol(currency)
+            );
+
+            let mut req = http_request_get(&url).map_err(|e| e.to_string())?;
+            req.headers_mut().insert("Accept", HeaderValue::from_static("application/json"));
+
+            let json: TickerResponse = fetch_json(req).await.map_err(|e| e.to_string())?;
+            if !json.error.is_empty() {
+                return Err(json.error.join(", "));
+            }
+
+            // The result is keyed by Kraken's own pair name (e.g. XETHZUSD)
+            let ticker = json.result.values().next().ok_or("Empty ticker result")?;
+            let price = ticker.c.first().ok_or("Missing last trade")?;
+
+            Ok(Quote {
+                source: self.name(),
+                price: price.parse().map_err(|_| "Invalid last trade price")?,
+                timestamp: unix_now(),
+            })
+        })
+    }
+}
+
+/// Kraken uses its own tickers for a few assets.
+fn kraken_symbol(symbol: &str) -> String {
+    match symbol.to_uppercase().as_str() {
+        "BTC" => "XBT".to_string(),
+        "DOGE" => "XDG".to_string(),
+        other => other.to_string(),
+    }
+}
@@ -0,0 +1,109 @@
+//! Market statistics beyond the spot price, e.g. for risk dashboards and collateral factors.
+use crate::sources::{coinmarketcap, USD};
+use serde::{Deserialize, Serialize};
+
+/// Version of the [`MarketStats`] record, bumped whenever its layout changes.
+pub const STATS_VERSION: u8 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StatField {
+    MarketCap,
+    Volume24h,
+    CirculatingSupply,
+    TotalSupply,
+    Change24h,
+}
+
+impl StatField {
+    pub const ALL: [StatField; 5] = [
+        StatField::MarketCap,
+        StatField::Volume24h,
+        StatField::CirculatingSupply,
+        StatField::TotalSupply,
+        StatField::Change24h,
+    ];
+
+    /// Bit set in `MarketStatsData.fields` when the field was requested.
+    pub fn bit(&self) -> u32 {
+        1 << (*self as u32)
+    }
+}
+
+impl std::str::FromStr for StatField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "market_cap" => Ok(StatField::MarketCap),
+            "volume_24h" => Ok(StatField::Volume24h),
+            "circulating_supply" => Ok(StatField::CirculatingSupply),
+            "total_supply" => Ok(StatField::TotalSupply),
+            "change_24h" => Ok(StatField::Change24h),
+            _ => Err(format!(
+                "Invalid field '{}', expected market_cap, volume_24h, circulating_supply, total_supply or change_24h",
+                s
+            )),
+        }
+    }
+}
+
+/// Price and the requested statistics of one asset. Fields that weren't requested are left out.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MarketStats {
+    /// See [`STATS_VERSION`]
+    pub version: u8,
+    /// CoinMarketCap id the request resolved to
+    pub id: u64,
+    pub symbol: String,
+    /// Unix timestamp (seconds) of the statistics
+    pub timestamp: u64,
+    /// Price in USD
+    pub price: f64,
+    /// Market cap in USD
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub market_cap: Option<f64>,
+    /// Traded volume over the last 24 hours in USD
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume_24h: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub circulating_supply: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_supply: Option<f64>,
+    /// Price change over the last 24 hours, in percent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change_24h: Option<f64>,
+}
+
+impl MarketStats {
+    /// Bitmask of the fields present in the record, see [`StatField::bit`].
+    pub fn fields(&self) -> u32 {
+        [
+            (StatField::MarketCap, self.market_cap),
+            (StatField::Volume24h, self.volume_24h),
+            (StatField::CirculatingSupply, self.circulating_supply),
+            (StatField::TotalSupply, self.total_supply),
+            (StatField::Change24h, self.change_24h),
+        ]
+        .iter()
+        .filter(|(_, value)| value.is_some())
+        .fold(0, |mask, (field, _)| mask | field.bit())
+    }
+}
+
+pub async fn get_stats(id: u64, fields: &[StatField]) -> Result<MarketStats, String> {
+    let market = coinmarketcap::fetch_market(id, USD).await?;
+    let select = |field: StatField, value: f64| fields.contains(&field).then_some(value);
+
+    Ok(MarketStats {
+        version: STATS_VERSION,
+        id,
+        symbol: market.symbol,
+        timestamp: market.timestamp,
+        price: market.price,
+        market_cap: select(StatField::MarketCap, market.market_cap),
+        volume_24h: select(StatField::Volume24h, market.volume_24h),
+        circulating_supply: select(StatField::CirculatingSupply, market.circulating_supply),
+        total_supply: select(StatField::TotalSupply, market.total_supply),
+        change_24h: select(StatField::Change24h, market.change_24h),
+    })
+}
@@ -1,7 +1,15 @@
 use crate::bindings::wavs::worker::layer_types::{TriggerData, TriggerDataEthContractEvent};
+use crate::confidence::ConfidenceMethod;
+use crate::historical::HistoricalPrice;
+use crate::resolution::{MarketResolution, ResolutionStatus};
+use crate::stats::MarketStats;
+use crate::twap::TwapData;
+use crate::volatility::VolatilityData;
+use crate::{Output, PriceFeedData, RoundData};
 use alloy_sol_types::SolValue;
 use anyhow::Result;
 use wavs_wasi_chain::decode_event_log_data;
+use wavs_wasi_chain::ethereum::alloy_primitives::{aliases::U80, I256, U256};
 
 pub enum Destination {
     Ethereum,
@@ -20,14 +28,212 @@ pub fn decode_trigger_event(trigger_data: TriggerData) -> Result<(u64, Vec<u8>,
     }
 }
 
-pub fn encode_trigger_output(trigger_id: u64, output: impl AsRef<[u8]>) -> Vec<u8> {
-    solidity::DataWithId { triggerId: trigger_id, data: output.as_ref().to_vec().into() }
-        .abi_encode()
+/// Encodes the output as its `IPriceFeed` struct (or `ITypes.AvsOutputData` for market
+/// resolutions), wrapped in a `DataWithId`.
+pub fn encode_trigger_output(trigger_id: u64, output: &Output, decimals: u8) -> Result<Vec<u8>> {
+    let data = match output {
+        Output::Prices(feeds) => encode_prices(feeds, decimals)?,
+        Output::Twap(twaps) => encode_twaps(twaps, decimals)?,
+        Output::Round(round) => encode_round(round, decimals)?,
+        Output::Historical(prices) => encode_historical(prices, decimals)?,
+        Output::Resolution(resolution) => encode_resolution(resolution),
+        Output::Stats(stats) => encode_stats(stats, decimals)?,
+        Output::Volatility(volatilities) => encode_volatilities(volatilities, decimals)?,
+    };
+
+    Ok(solidity::DataWithId { triggerId: trigger_id, data: data.into() }.abi_encode())
+}
+
+/// `IPriceFeed.PriceData[]`
+fn encode_prices(feeds: &[PriceFeedData], decimals: u8) -> Result<Vec<u8>> {
+    let prices = feeds
+        .iter()
+        .map(|feed| {
+            Ok(solidity::PriceData {
+                id: feed.id,
+                symbol: feed.symbol.clone(),
+                currency: feed.currency.clone(),
+                price: scale_price(feed.price, decimals)?,
+                conf: scale_price(feed.confidence, decimals)?,
+                decimals,
+                timestamp: feed.timestamp,
+                sources: feed.sources.try_into()?,
+                confMethod: feed.confidence_method.id(),
+                derived: feed.derived,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(prices.abi_encode())
+}
+
+/// `IPriceFeed.TwapData[]`
+fn encode_twaps(twaps: &[TwapData], decimals: u8) -> Result<Vec<u8>> {
+    let twaps = twaps
+        .iter()
+        .map(|twap| {
+            Ok(solidity::TwapData {
+                id: twap.id,
+                symbol: twap.symbol.clone(),
+                price: scale_price(twap.price, decimals)?,
+                decimals,
+                samples: twap.samples,
+                windowStart: twap.window_start,
+                windowEnd: twap.window_end,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(twaps.abi_encode())
+}
+
+/// `IPriceFeed.RoundData`
+fn encode_round(round: &RoundData, decimals: u8) -> Result<Vec<u8>> {
+    Ok(solidity::RoundData {
+        roundId: U80::from(round.round_id),
+        answer: I256::try_from(scale_price(round.answer, decimals)?)?,
+        startedAt: U256::from(round.started_at),
+        updatedAt: U256::from(round.updated_at),
+        answeredInRound: U80::from(round.answered_in_round),
+    }
+    .abi_encode())
+}
+
+/// `IPriceFeed.HistoricalPriceData[]`
+fn encode_historical(prices: &[HistoricalPrice], decimals: u8) -> Result<Vec<u8>> {
+    let prices = prices
+        .iter()
+        .map(|price| {
+            Ok(solidity::HistoricalPriceData {
+                id: price.id,
+                symbol: price.symbol.clone(),
+                price: scale_price(price.price, decimals)?,
+                decimals,
+                targetTimestamp: price.target_timestamp,
+                sampleTimestamp: price.sample_timestamp,
+                distance: price.distance,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(prices.abi_encode())
+}
+
+/// `ITypes.AvsOutputData`, as decoded by the prediction market controller
+fn encode_resolution(resolution: &MarketResolution) -> Vec<u8> {
+    let (status, payouts, retry_after, reason) = match &resolution.status {
+        ResolutionStatus::Resolved { payouts, .. } => (
+            solidity::ResolutionStatus::Resolved,
+            payouts.iter().map(|payout| U256::from(*payout)).collect(),
+            0,
+            String::new(),
+        ),
+        ResolutionStatus::NotReady { reason, retry_after } => {
+            (solidity::ResolutionStatus::NotReady, Vec::new(), *retry_after, reason.clone())
+        }
+    };
+    solidity::AvsOutputData {
+        status,
+        questionId: resolution.question_id,
+        payouts,
+        startTime: resolution.settlement_time,
+        retryAfter: retry_after,
+        reason,
+    }
+    .abi_encode()
+}
+
+/// `IPriceFeed.MarketStatsData[]`. Fields that weren't requested are zero and unset in `fields`.
+fn encode_stats(stats: &[MarketStats], decimals: u8) -> Result<Vec<u8>> {
+    let scale = |value: Option<f64>| scale_price(value.unwrap_or_default(), decimals);
+    let stats = stats
+        .iter()
+        .map(|stats| {
+            Ok(solidity::MarketStatsData {
+                version: stats.version,
+                id: stats.id,
+                symbol: stats.symbol.clone(),
+                timestamp: stats.timestamp,
+                decimals,
+                fields: stats.fields(),
+                price: scale_price(stats.price, decimals)?,
+                marketCap: scale(stats.market_cap)?,
+                volume24h: scale(stats.volume_24h)?,
+                circulatingSupply: scale(stats.circulating_supply)?,
+                totalSupply: scale(stats.total_supply)?,
+                change24hBps: (stats.change_24h.unwrap_or_default() * 100.0).round() as i32,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(stats.abi_encode())
+}
+
+/// `IPriceFeed.VolatilityData[]`
+fn encode_volatilities(volatilities: &[VolatilityData], decimals: u8) -> Result<Vec<u8>> {
+    let volatilities = volatilities
+        .iter()
+        .map(|volatility| {
+            Ok(solidity::VolatilityData {
+                id: volatility.id,
+                symbol: volatility.symbol.clone(),
+                volatility: scale_price(volatility.volatility, decimals)?,
+                decimals,
+                samples: volatility.samples,
+                windowStart: volatility.window_start,
+                windowEnd: volatility.window_end,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(volatilities.abi_encode())
+}
+
+/// Decodes an `IPriceFeed.PriceData[]` payload back into price feeds.
+pub fn decode_prices(data: &[u8]) -> Result<Vec<PriceFeedData>> {
+    let prices = Vec::<solidity::PriceData>::abi_decode(data, true)?;
+
+    prices
+        .into_iter()
+        .map(|price| {
+            Ok(PriceFeedData {
+                id: price.id,
+                symbol: price.symbol,
+                currency: price.currency,
+                derived: price.derived,
+                price: unscale_price(price.price, price.decimals)?,
+                confidence: unscale_price(price.conf, price.decimals)?,
+                confidence_method: ConfidenceMethod::from_id(price.confMethod)
+                    .map_err(|e| anyhow::anyhow!(e))?,
+                timestamp: price.timestamp,
+                sources: price.sources.into(),
+            })
+        })
+        .collect()
+}
+
+/// Converts a floating point price into an integer scaled by `10^decimals`.
+pub fn scale_price(price: f64, decimals: u8) -> Result<U256> {
+    let scaled = (price * 10f64.powi(decimals as i32)).round();
+    if !scaled.is_finite() || scaled < 0.0 || scaled >= u128::MAX as f64 {
+        return Err(anyhow::anyhow!("Price {} cannot be scaled to {} decimals", price, decimals));
+    }
+    Ok(U256::from(scaled as u128))
+}
+
+/// Inverse of [`scale_price`].
+pub fn unscale_price(price: U256, decimals: u8) -> Result<f64> {
+    let price: u128 = price.try_into()?;
+    Ok(price as f64 / 10f64.powi(decimals as i32))
+}
+
+/// Parses an RFC 3339 timestamp (e.g. `2025-01-29T20:21:54.662Z`) into unix seconds.
+pub fn parse_timestamp(timestamp: &str) -> Result<u64> {
+    let parsed = chrono::DateTime::parse_from_rfc3339(timestamp)?;
+    u64::try_from(parsed.timestamp())
+        .map_err(|_| anyhow::anyhow!("Timestamp before unix epoch: {}", timestamp))
 }
 
 mod solidity {
     use alloy_sol_macro::sol;
+    pub use IPriceFeed::*;
     pub use ITypes::*;
 
     sol!("../../src/interfaces/ITypes.sol");
+    sol!("../../src/interfaces/IPriceFeed.sol");
 }
@@ -0,0 +1,58 @@
+use crate::request::Window;
+use crate::sources::coinmarketcap;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TwapData {
+    /// CoinMarketCap id the request resolved to
+    pub id: u64,
+    pub symbol: String,
+    /// Time-weighted average price in USD
+    pub price: f64,
+    /// Number of chart points used
+    pub samples: u32,
+    /// Unix timestamp (seconds) of the first point used
+    pub window_start: u64,
+    /// Unix timestamp (seconds) of the last point used
+    pub window_end: u64,
+}
+
+pub async fn get_twap(id: u64, window: Window) -> Result<TwapData, String> {
+    let asset = coinmarketcap::fetch_asset(id).await?;
+    let points = coinmarketcap::fetch_chart(id, window.chart_range()).await?;
+
+    let latest = points.last().map(|(timestamp, _)| *timestamp).ok_or("Empty chart data")?;
+    let window_start = latest.saturating_sub(window.seconds());
+    let points: Vec<(u64, f64)> =
+        points.into_iter().filter(|(timestamp, _)| *timestamp >= window_start).collect();
+
+    let price = time_weighted_average(&points)
+        .ok_or_else(|| format!("Not enough chart points for {}", asset.symbol))?;
+
+    Ok(TwapData {
+        id,
+        symbol: asset.symbol,
+        price,
+        samples: points.len() as u32,
+        window_start: points[0].0,
+        window_end: latest,
+    })
+}
+
+/// Averages `(timestamp, price)` points sorted by time, weighting each price by how long it
+/// held until the next point.
+fn time_weighted_average(points: &[(u64, f64)]) -> Option<f64> {
+    if points.len() < 2 {
+        return None;
+    }
+
+    let (weighted, duration) = points.windows(2).fold((0.0, 0u64), |(sum, total), pair| {
+        let elapsed = pair[1].0 - pair[0].0;
+        (sum + pair[0].1 * elapsed as f64, total + elapsed)
+    });
+
+    if duration == 0 {
+        return None;
+    }
+    Some(weighted / duration as f64)
+}
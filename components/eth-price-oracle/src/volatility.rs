@@ -0,0 +1,66 @@
+use crate::request::Window;
+use crate::sources::coinmarketcap;
+use serde::{Deserialize, Serialize};
+
+const SECONDS_PER_YEAR: f64 = 365.0 * 24.0 * 60.0 * 60.0;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VolatilityData {
+    /// CoinMarketCap id the request resolved to
+    pub id: u64,
+    pub symbol: String,
+    /// Annualized realized volatility as a fraction, e.g. `0.65` for 65%
+    pub volatility: f64,
+    /// Number of chart points used
+    pub samples: u32,
+    /// Unix timestamp (seconds) of the first point used
+    pub window_start: u64,
+    /// Unix timestamp (seconds) of the last point used
+    pub window_end: u64,
+}
+
+/// Annualized realized volatility from the log returns of the chart points in the window.
+pub async fn get_realized_volatility(id: u64, window: Window) -> Result<VolatilityData, String> {
+    let asset = coinmarketcap::fetch_asset(id).await?;
+    let points = coinmarketcap::fetch_chart(id, window.chart_range()).await?;
+
+    let latest = points.last().map(|(timestamp, _)| *timestamp).ok_or("Empty chart data")?;
+    let window_start = latest.saturating_sub(window.seconds());
+    let points: Vec<(u64, f64)> =
+        points.into_iter().filter(|(timestamp, _)| *timestamp >= window_start).collect();
+
+    let prices: Vec<f64> = points.iter().map(|(_, price)| *price).collect();
+    let std = log_return_std(&prices)
+        .ok_or_else(|| format!("Not enough chart points for {}", asset.symbol))?;
+
+    // Scale the per-sample deviation by the number of sampling intervals in a year
+    let elapsed = latest - points[0].0;
+    let interval = elapsed as f64 / (points.len() - 1) as f64;
+    let volatility = std * (SECONDS_PER_YEAR / interval).sqrt();
+
+    Ok(VolatilityData {
+        id,
+        symbol: asset.symbol,
+        volatility,
+        samples: points.len() as u32,
+        window_start: points[0].0,
+        window_end: latest,
+    })
+}
+
+/// Sample standard deviation of the log returns between consecutive prices.
+pub fn log_return_std(prices: &[f64]) -> Option<f64> {
+    let returns: Vec<f64> = prices
+        .windows(2)
+        .filter(|pair| pair[0] > 0.0 && pair[1] > 0.0)
+        .map(|pair| (pair[1] / pair[0]).ln())
+        .collect();
+    if returns.len() < 2 {
+        return None;
+    }
+
+    let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+    let variance =
+        returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+    Some(variance.sqrt())
+}
@@ -0,0 +1,186 @@
+//! Sandboxed Rhai engine running untrusted resolution scripts.
+use crate::helpers::register_helpers;
+use crate::sport::Game;
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Dynamic, Engine, EvalAltResult, Map, Scope};
+use serde_json::Value;
+use wavs_wasi_chain::ethereum::alloy_primitives::U256;
+
+/// Enough for scripts comparing a few dozen fields, far below the component's fuel budget
+const MAX_OPERATIONS: u64 = 100_000;
+const MAX_CALL_LEVELS: usize = 16;
+const MAX_EXPR_DEPTH: usize = 32;
+const MAX_FUNCTION_EXPR_DEPTH: usize = 16;
+const MAX_VARIABLES: usize = 256;
+const MAX_FUNCTIONS: usize = 32;
+const MAX_STRING_SIZE: usize = 4 * 1024;
+pub const MAX_ARRAY_SIZE: usize = 256;
+const MAX_MAP_SIZE: usize = 256;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ScriptError {
+    /// The script doesn't compile, exceeds a sandbox limit or fails while running
+    #[error("Script rejected: {0}")]
+    Rejected(String),
+    /// The script called `throw`, e.g. because the match has not ended yet
+    #[error("Script threw: {0}")]
+    Thrown(String),
+    /// The script ran but didn't return `#{ outcome, payout }`
+    #[error("Invalid script output: {0}")]
+    InvalidOutput(String),
+}
+
+impl From<Box<EvalAltResult>> for ScriptError {
+    fn from(err: Box<EvalAltResult>) -> Self {
+        match err.unwrap_inner() {
+            EvalAltResult::ErrorRuntime(value, _) => ScriptError::Thrown(value.to_string()),
+            other => ScriptError::Rejected(other.to_string()),
+        }
+    }
+}
+
+/// An engine with bounded operations, call depth and data sizes, without `eval` or modules.
+pub fn sandboxed_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_call_levels(MAX_CALL_LEVELS)
+        .set_max_expr_depths(MAX_EXPR_DEPTH, MAX_FUNCTION_EXPR_DEPTH)
+        .set_max_variables(MAX_VARIABLES)
+        .set_max_functions(MAX_FUNCTIONS)
+        .set_max_string_size(MAX_STRING_SIZE)
+        .set_max_array_size(MAX_ARRAY_SIZE)
+        .set_max_map_size(MAX_MAP_SIZE)
+        .set_max_modules(0)
+        .set_module_resolver(DummyModuleResolver::new())
+        .disable_symbol("eval")
+        .disable_symbol("import");
+    engine
+}
+
+/// Exposes the game to scripts, both as a whole in `game` and as flat shortcuts for the most
+/// used fields and the sport-specific extras.
+pub fn build_rhai_scope(game: &Game) -> Result<Scope<'static>, Box<EvalAltResult>> {
+    let mut scope = Scope::new();
+
+    // Whole game as nested maps and arrays, e.g. `game.periods[0].home_score`
+    scope.push("game", rhai::serde::to_dynamic(game)?);
+
+    scope.push("sport", rhai::serde::to_dynamic(game.sport)?);
+    scope.push("match_id", game.id.clone());
+    scope.push("start_time", game.start_time.clone());
+    scope.push("status", game.status.clone());
+    scope.push("match_status", game.match_status.clone());
+
+    // Scores
+    scope.push("home_score", game.home.score);
+    scope.push("away_score", game.away.score);
+
+    // Winner ID (may be null)
+    if let Some(winner_id) = &game.winner_id {
+        scope.push("winner_id", winner_id.clone());
+    }
+
+    // Competitors (IDs and names)
+    scope.push("home_team", game.home.name.clone());
+    scope.push("home_team_id", game.home.id.clone());
+    scope.push("away_team", game.away.name.clone());
+    scope.push("away_team_id", game.away.id.clone());
+
+    // Sport-specific extras, skipping those the API didn't report
+    if let Value::Object(extras) = &game.extras {
+        for (name, value) in extras.iter().filter(|(_, value)| !value.is_null()) {
+            scope.push(name.clone(), rhai::serde::to_dynamic(value)?);
+        }
+    }
+
+    Ok(scope)
+}
+
+/// Runs a resolution script against the match, returning its `(outcome, payout)`.
+///
+/// Payout entries are non-negative integers, or decimal strings for values beyond the script's
+/// 64-bit integers. They must have one entry per outcome of the market and must not all be zero.
+pub fn evaluate_rhai_script(
+    game: &Game,
+    script: &str,
+    outcomes: usize,
+) -> Result<(String, Vec<U256>), ScriptError> {
+    let mut engine = sandboxed_engine();
+    // Rhai counts the sizes of nested data, so the summary alone would exceed the limits. They
+    // are raised to the size of the game, letting scripts read it but not build anything larger.
+    let exposed = serde_json::to_value(game).map_err(|e| ScriptError::Rejected(e.to_string()))?;
+    let (arrays, maps, strings) = data_sizes(&exposed);
+    engine
+        .set_max_array_size(MAX_ARRAY_SIZE.max(arrays))
+        .set_max_map_size(MAX_MAP_SIZE.max(maps))
+        .set_max_string_size(MAX_STRING_SIZE.max(strings));
+    register_helpers(&mut engine, game);
+    let ast = engine.compile(script).map_err(|e| ScriptError::Rejected(e.to_string()))?;
+
+    let mut scope = build_rhai_scope(game)?;
+    let result: Dynamic = engine.eval_ast_with_scope(&mut scope, &ast)?;
+    let map = result
+        .try_cast::<Map>()
+        .ok_or_else(|| ScriptError::InvalidOutput("expected a map".to_string()))?;
+
+    let outcome = map
+        .get("outcome")
+        .and_then(|v| v.clone().try_cast::<String>())
+        .ok_or_else(|| ScriptError::InvalidOutput("missing or invalid outcome".to_string()))?;
+
+    let payout = map
+        .get("payout")
+        .and_then(|v| v.clone().try_cast::<rhai::Array>())
+        .ok_or_else(|| ScriptError::InvalidOutput("missing or invalid payout".to_string()))?;
+
+    let payouts = payout.iter().map(to_payout).collect::<Result<Vec<_>, _>>()?;
+    validate_payouts(&payouts, outcomes)?;
+    Ok((outcome, payouts))
+}
+
+/// Total array entries, map entries and string bytes of a value and everything it contains, as
+/// Rhai counts them against its data limits.
+fn data_sizes(value: &Value) -> (usize, usize, usize) {
+    let add = |(a, m, s): (usize, usize, usize), (da, dm, ds)| (a + da, m + dm, s + ds);
+    match value {
+        Value::Array(items) => {
+            items.iter().fold((0, 0, 0), |total, item| add(total, add((1, 0, 0), data_sizes(item))))
+        }
+        Value::Object(entries) => entries
+            .values()
+            .fold((0, 0, 0), |total, entry| add(total, add((0, 1, 0), data_sizes(entry)))),
+        Value::String(text) => (0, 0, text.len()),
+        _ => (0, 0, 0),
+    }
+}
+
+fn to_payout(value: &Dynamic) -> Result<U256, ScriptError> {
+    let invalid = || ScriptError::InvalidOutput(format!("invalid payout {}", value));
+    if let Ok(int) = value.as_int() {
+        return u64::try_from(int).map(U256::from).map_err(|_| invalid());
+    }
+    match value.clone().into_immutable_string() {
+        Ok(text) => text.trim().parse().map_err(|_| invalid()),
+        Err(_) => Err(invalid()),
+    }
+}
+
+fn validate_payouts(payouts: &[U256], outcomes: usize) -> Result<(), ScriptError> {
+    if payouts.len() != outcomes {
+        return Err(ScriptError::InvalidOutput(format!(
+            "expected {} payouts, got {}",
+            outcomes,
+            payouts.len()
+        )));
+    }
+
+    let total = payouts
+        .iter()
+        .try_fold(U256::ZERO, |total, payout| total.checked_add(*payout))
+        .ok_or_else(|| ScriptError::InvalidOutput("payouts overflow uint256".to_string()))?;
+    if total.is_zero() {
+        return Err(ScriptError::InvalidOutput("payouts are all zero".to_string()));
+    }
+    Ok(())
+}
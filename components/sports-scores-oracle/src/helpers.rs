@@ -0,0 +1,117 @@
+//! Native functions registered for resolution scripts, so markets don't hand-roll the usual
+//! score comparisons and payout vectors.
+use crate::engine::MAX_ARRAY_SIZE;
+use crate::sport::Game;
+use crate::status::EventStatus;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, Position, FLOAT, INT};
+
+/// Sum of the payouts returned by `payout_scalar`
+const SCALAR_PRECISION: INT = 1_000_000;
+
+/// Registers the helpers, bound to the match the script resolves:
+///
+/// - `period_score(n)`: `#{ home, away }` of period `n`, or `()` if it wasn't played
+/// - `goal_diff()`, `total_goals()`: home minus away and home plus away scores
+/// - `is_final()`, `is_cancelled()`: whether the match ended or was called off
+/// - `is_walkover()`: whether the match was awarded without being played to the end, e.g. a
+///   walkover or a tennis retirement
+/// - `awarded()`: index of the competitor awarded the win of a walkover or abandoned match, home
+///   0 and away 1, or `()` if there is none
+/// - `payout_winner(idx, n)`: `n` payouts all going to outcome `idx`
+/// - `payout_cancel(n)`: `n` equal payouts, refunding every outcome
+/// - `payout_scalar(value, lo, hi, n)`: payouts of `value` between `n` outcomes evenly spaced
+///   from `lo` to `hi`, clamped to the range
+/// - `parse_time(s)`: unix seconds of an ISO-8601 time such as `start_time`
+pub fn register_helpers(engine: &mut Engine, game: &Game) {
+    let (home, away) = (game.home.score, game.away.score);
+    let periods: Vec<(INT, INT, INT)> =
+        game.periods.iter().map(|p| (p.number as INT, p.home_score, p.away_score)).collect();
+    let is_final = game.event_status().is_final();
+    let is_cancelled = game.event_status().is_cancelled();
+    let is_walkover = game.event_status() == EventStatus::Walkover;
+    let awarded = match game.event_status() {
+        EventStatus::Walkover | EventStatus::Abandoned => game.awarded_index(),
+        _ => None,
+    };
+
+    engine
+        .register_fn("period_score", move |n: INT| -> Dynamic {
+            match periods.iter().find(|(number, _, _)| *number == n) {
+                Some(&(_, home, away)) => {
+                    let mut score = Map::new();
+                    score.insert("home".into(), home.into());
+                    score.insert("away".into(), away.into());
+                    score.into()
+                }
+                None => Dynamic::UNIT,
+            }
+        })
+        .register_fn("goal_diff", move || home - away)
+        .register_fn("total_goals", move || home + away)
+        .register_fn("is_final", move || is_final)
+        .register_fn("is_cancelled", move || is_cancelled)
+        .register_fn("is_walkover", move || is_walkover)
+        .register_fn("awarded", move || awarded.map_or(Dynamic::UNIT, |idx| (idx as INT).into()))
+        .register_fn("payout_winner", payout_winner)
+        .register_fn("payout_cancel", payout_cancel)
+        .register_fn("payout_scalar", payout_scalar)
+        .register_fn("payout_scalar", |value: INT, lo: INT, hi: INT, n: INT| {
+            payout_scalar(value as FLOAT, lo as FLOAT, hi as FLOAT, n)
+        })
+        .register_fn("parse_time", parse_time);
+}
+
+fn payout_winner(idx: INT, n: INT) -> Result<Array, Box<EvalAltResult>> {
+    let mut payouts = vec![Dynamic::from_int(0); outcome_count(n, 1)?];
+    let slot = payouts
+        .get_mut(usize::try_from(idx).unwrap_or(usize::MAX))
+        .ok_or(EvalAltResult::ErrorArrayBounds(n as usize, idx, Position::NONE))?;
+    *slot = Dynamic::from_int(1);
+    Ok(payouts)
+}
+
+fn payout_cancel(n: INT) -> Result<Array, Box<EvalAltResult>> {
+    Ok(vec![Dynamic::from_int(1); outcome_count(n, 1)?])
+}
+
+/// Splits [`SCALAR_PRECISION`] between the two outcomes surrounding `value`, in proportion to
+/// its distance to each. With two outcomes, this is the `[short, long]` payout of a scalar market.
+fn payout_scalar(value: FLOAT, lo: FLOAT, hi: FLOAT, n: INT) -> Result<Array, Box<EvalAltResult>> {
+    let count = outcome_count(n, 2)?;
+    if !lo.is_finite() || !hi.is_finite() || lo >= hi {
+        return Err(invalid_argument(format!("Scalar range is empty: {} to {}", lo, hi)));
+    }
+    if value.is_nan() {
+        return Err(invalid_argument("Scalar value is NaN".to_string()));
+    }
+
+    let position = ((value - lo) / (hi - lo)).clamp(0.0, 1.0) * (count - 1) as FLOAT;
+    let lower = (position.floor() as usize).min(count - 2);
+    let upper_share = ((position - lower as FLOAT) * SCALAR_PRECISION as FLOAT).round() as INT;
+
+    let mut payouts = vec![Dynamic::from_int(0); count];
+    payouts[lower] = Dynamic::from_int(SCALAR_PRECISION - upper_share);
+    payouts[lower + 1] = Dynamic::from_int(upper_share);
+    Ok(payouts)
+}
+
+/// Parses an ISO-8601 time with an offset (e.g. `2025-03-01T15:00:00+00:00`) into unix seconds.
+fn parse_time(time: &str) -> Result<INT, Box<EvalAltResult>> {
+    chrono::DateTime::parse_from_rfc3339(time).map(|t| t.timestamp()).map_err(|e| {
+        EvalAltResult::ErrorSystem(format!("Invalid ISO-8601 time '{}'", time), Box::new(e)).into()
+    })
+}
+
+/// Checks that a market has between `min` and the maximum array size outcomes.
+fn outcome_count(n: INT, min: usize) -> Result<usize, Box<EvalAltResult>> {
+    usize::try_from(n).ok().filter(|n| (min..=MAX_ARRAY_SIZE).contains(n)).ok_or_else(|| {
+        invalid_argument(format!(
+            "Outcome count must be between {} and {}, got {}",
+            min, MAX_ARRAY_SIZE, n
+        ))
+    })
+}
+
+fn invalid_argument(message: String) -> Box<EvalAltResult> {
+    EvalAltResult::ErrorArithmetic(message, Position::NONE).into()
+}
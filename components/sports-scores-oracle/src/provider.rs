@@ -0,0 +1,129 @@
+//! Providers of game results. Markets are resolved from Sportradar, cross-checked against a
+//! second provider when the trigger names the fixture there.
+mod sportradar;
+mod thesportsdb;
+
+pub use sportradar::Sportradar;
+pub use thesportsdb::TheSportsDb;
+
+use crate::sport::Game;
+use crate::status::Phase;
+use futures::future::LocalBoxFuture;
+use std::collections::HashMap;
+
+/// A provider able to fetch a game by its own fixture id.
+pub trait ScoresProvider {
+    /// Identifier used in logs and disagreement reasons
+    fn name(&self) -> &'static str;
+
+    fn fetch_game<'a>(&'a self, game_id: &'a str) -> LocalBoxFuture<'a, Result<Game, String>>;
+}
+
+/// Ids of the same teams at the second provider, keyed by Sportradar competitor id, read from
+/// `WAVS_ENV_TEAM_IDS`, e.g. `sr:competitor:17=133613,sr:competitor:44=133602`.
+#[derive(Debug, Clone, Default)]
+pub struct TeamIds(HashMap<String, String>);
+
+impl TeamIds {
+    pub fn from_env() -> Result<Self, String> {
+        let Ok(value) = std::env::var("WAVS_ENV_TEAM_IDS") else {
+            return Ok(Self::default());
+        };
+        value
+            .split(',')
+            .filter(|pair| !pair.trim().is_empty())
+            .map(|pair| match pair.split_once('=') {
+                Some((ours, theirs)) => Ok((ours.trim().to_string(), theirs.trim().to_string())),
+                None => Err(format!("Invalid WAVS_ENV_TEAM_IDS entry '{}'", pair)),
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+/// Whether markets must name the fixture at the second provider, from
+/// `WAVS_ENV_REQUIRE_CROSS_CHECK`. Required unless operators turn it off.
+pub fn cross_check_required() -> Result<bool, String> {
+    match std::env::var("WAVS_ENV_REQUIRE_CROSS_CHECK") {
+        Ok(value) => value
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid value for WAVS_ENV_REQUIRE_CROSS_CHECK: '{}'", value)),
+        Err(_) => Ok(true),
+    }
+}
+
+/// Why a game couldn't be cross-checked.
+#[derive(Debug, thiserror::Error)]
+pub enum CrossCheckError {
+    /// `WAVS_ENV_TEAM_IDS` lacks a team of the game, which only the operator can fix
+    #[error("No {provider} id mapped for {side} team {team} in WAVS_ENV_TEAM_IDS")]
+    Unmapped { provider: &'static str, side: &'static str, team: String },
+    /// The providers report different teams, phases, scores or winners
+    #[error("{0}")]
+    Disagreement(String),
+}
+
+/// Checks that both providers report the same teams, the same phase of the match and, once it
+/// is final, the same score, or for a walkover the same winner. Returns why they disagree
+/// otherwise.
+pub fn compare_games(
+    primary: &dyn ScoresProvider,
+    ours: &Game,
+    secondary: &dyn ScoresProvider,
+    theirs: &Game,
+    teams: &TeamIds,
+) -> Result<(), CrossCheckError> {
+    for (side, our_team, their_team) in
+        [("home", &ours.home, &theirs.home), ("away", &ours.away, &theirs.away)]
+    {
+        let mapped = teams.0.get(&our_team.id).ok_or_else(|| CrossCheckError::Unmapped {
+            provider: secondary.name(),
+            side,
+            team: our_team.id.clone(),
+        })?;
+        if *mapped != their_team.id {
+            return Err(CrossCheckError::Disagreement(format!(
+                "{} team differs: {} maps to {}, {} reports {}",
+                side,
+                our_team.id,
+                mapped,
+                secondary.name(),
+                their_team.id
+            )));
+        }
+    }
+
+    let phase = ours.event_status().phase();
+    if phase != theirs.event_status().phase() {
+        return Err(CrossCheckError::Disagreement(format!(
+            "Status differs: {} reports {}, {} reports {}",
+            primary.name(),
+            ours.status,
+            secondary.name(),
+            theirs.status
+        )));
+    }
+    let score = |game: &Game| (game.home.score, game.away.score);
+    if phase == Phase::Final && score(ours) != score(theirs) {
+        return Err(CrossCheckError::Disagreement(format!(
+            "Final score differs: {} reports {}-{}, {} reports {}-{}",
+            primary.name(),
+            ours.home.score,
+            ours.away.score,
+            secondary.name(),
+            theirs.home.score,
+            theirs.away.score
+        )));
+    }
+    if phase == Phase::Walkover && ours.awarded_index() != theirs.awarded_index() {
+        return Err(CrossCheckError::Disagreement(format!(
+            "Awarded winner differs: {} reports {}, {} reports {}",
+            primary.name(),
+            ours.winner_id.as_deref().unwrap_or("none"),
+            secondary.name(),
+            theirs.winner_id.as_deref().unwrap_or("none")
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use super::ScoresProvider;
+use crate::sport::{Game, Sport};
+use futures::future::LocalBoxFuture;
+
+/// <https://developer.sportradar.com>, through the product of the game's sport.
+pub struct Sportradar {
+    pub sport: Sport,
+    pub api_key: String,
+}
+
+impl ScoresProvider for Sportradar {
+    fn name(&self) -> &'static str {
+        "sportradar"
+    }
+
+    fn fetch_game<'a>(&'a self, game_id: &'a str) -> LocalBoxFuture<'a, Result<Game, String>> {
+        Box::pin(self.sport.fetch_game(game_id, &self.api_key))
+    }
+}
@@ -0,0 +1,107 @@
+use crate::sport::Sport;
+use std::collections::HashSet;
+use wavs_wasi_chain::ethereum::alloy_primitives::B256;
+
+/// `ConditionalTokens` conditions have at least two outcome slots
+pub const MIN_OUTCOMES: usize = 2;
+
+/// A trigger input of the form `GAME_ID|API_KEY[|key=value...]`, e.g.
+/// `sr:sport_event:50850237|KEY|question=0x...|cid=bafkrei...|outcomes=2|sport=soccer|fixture=2070151|settle=auto`.
+#[derive(Debug, Clone)]
+pub struct ScoresRequest {
+    pub game_id: String,
+    pub api_key: String,
+    /// Question the payouts are reported for, required to submit on chain
+    pub question_id: Option<B256>,
+    /// CID of the Rhai resolution script, the built-in match winner script when unset
+    pub script_cid: Option<String>,
+    /// Number of outcome slots of the market, checked against the payouts. Required with a CID,
+    /// the built-in script's own count otherwise
+    pub outcomes: Option<usize>,
+    /// Sportradar product the game is fetched from, soccer by default
+    pub sport: Sport,
+    /// Id of the same fixture at the second provider, to cross-check the result against
+    pub fixture_id: Option<String>,
+    /// Whether walkovers and other awarded wins are paid from their status before a custom script
+    /// runs, with `settle=auto`. Needs a market whose outcome 0 and 1 are the home and away wins.
+    pub auto_settle: bool,
+}
+
+impl ScoresRequest {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        // Trailing NUL bytes are ignored so `bytes32` padded inputs keep working
+        let input = input.trim_end_matches('\0').trim();
+        let mut parts = input.split('|');
+
+        let game_id = parts.next().map(str::trim).unwrap_or_default();
+        let api_key = parts.next().map(str::trim).unwrap_or_default();
+        if game_id.is_empty() || api_key.is_empty() {
+            return Err(
+                "Invalid input format. Expected 'GAME_ID|API_KEY[|key=value...]'".to_string()
+            );
+        }
+
+        let mut question_id = None;
+        let mut script_cid = None;
+        let mut outcomes = None;
+        let mut sport = Sport::default();
+        let mut fixture_id = None;
+        let mut auto_settle = false;
+        let mut seen = HashSet::new();
+        for option in parts {
+            let (key, value) = option
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid option '{}', expected key=value", option))?;
+            // The controller appends `question`, which an earlier option must not shadow
+            if !seen.insert(key.trim()) {
+                return Err(format!("Duplicate option '{}'", key.trim()));
+            }
+            match key.trim() {
+                "question" => {
+                    question_id = Some(
+                        value
+                            .trim()
+                            .parse()
+                            .map_err(|_| format!("Invalid question: '{}'", value))?,
+                    )
+                }
+                "cid" => script_cid = Some(value.trim().to_string()),
+                "outcomes" => {
+                    outcomes = Some(
+                        value
+                            .trim()
+                            .parse()
+                            .ok()
+                            .filter(|count| *count >= MIN_OUTCOMES)
+                            .ok_or_else(|| format!("Invalid outcomes: '{}'", value))?,
+                    )
+                }
+                "sport" => sport = value.trim().parse()?,
+                "fixture" => fixture_id = Some(value.trim().to_string()),
+                "settle" => {
+                    auto_settle = match value.trim() {
+                        "auto" => true,
+                        "script" => false,
+                        _ => return Err(format!("Invalid settle: '{}'", value)),
+                    }
+                }
+                other => return Err(format!("Unknown option '{}'", other)),
+            }
+        }
+
+        if script_cid.is_some() && outcomes.is_none() {
+            return Err("'outcomes' is required with a script 'cid'".to_string());
+        }
+
+        Ok(Self {
+            game_id: game_id.to_string(),
+            api_key: api_key.to_string(),
+            question_id,
+            script_cid,
+            outcomes,
+            sport,
+            fixture_id,
+            auto_settle,
+        })
+    }
+}
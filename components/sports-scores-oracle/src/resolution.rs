@@ -0,0 +1,134 @@
+//! The result of settling a market: resolved, not ready yet, or invalid.
+use crate::engine::{evaluate_rhai_script, ScriptError};
+use crate::sport::Game;
+use crate::status::Settlement;
+use serde::Serialize;
+use wavs_wasi_chain::ethereum::alloy_primitives::{B256, U256};
+
+/// Expected length of a match including breaks, after which its result should be available
+const MATCH_DURATION_SECS: u64 = 2 * 60 * 60;
+/// Minimum wait before a market that isn't ready is triggered again
+const MIN_RETRY_SECS: u64 = 15 * 60;
+
+/// The state of a market, as submitted in `ITypes.AvsOutputData`.
+#[derive(Debug, Serialize)]
+pub struct Resolution {
+    pub question_id: B256,
+    /// Unix timestamp (seconds) at which the match is scheduled to start, 0 if unknown
+    pub start_time: u64,
+    #[serde(flatten)]
+    pub status: ResolutionStatus,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ResolutionStatus {
+    Resolved {
+        /// Label returned by the script, e.g. the winning team
+        outcome: String,
+        /// Payout numerators, one per outcome slot
+        payouts: Vec<U256>,
+    },
+    /// The script threw, typically because the match hasn't ended yet, or the match is paused
+    NotReady {
+        reason: String,
+        /// Unix timestamp (seconds) after which the market should be triggered again
+        retry_after: u64,
+    },
+    /// The script was rejected or returned an invalid output, or the market can't be settled
+    Invalid { reason: String },
+    /// The providers report different teams, statuses or final scores
+    Disagreement {
+        reason: String,
+        /// Unix timestamp (seconds) after which the market should be triggered again
+        retry_after: u64,
+    },
+}
+
+/// Settles the market from the status of the match, running its script when it was played.
+/// Called off matches are refunded whatever the slot layout. Awarded wins are left to the script
+/// unless `auto_settle` is set, as only the market knows which slot they pay.
+pub fn resolve_market(
+    game: &Game,
+    script: &str,
+    outcomes: usize,
+    auto_settle: bool,
+    start_time: u64,
+    now: u64,
+) -> ResolutionStatus {
+    let status = &game.status;
+    match game.event_status().settlement(game) {
+        Settlement::Award(_) if !auto_settle => {
+            let result = evaluate_rhai_script(game, script, outcomes);
+            ResolutionStatus::from_script(result, start_time, now)
+        }
+        Settlement::Script => {
+            let result = evaluate_rhai_script(game, script, outcomes);
+            ResolutionStatus::from_script(result, start_time, now)
+        }
+        Settlement::Retry => {
+            ResolutionStatus::not_ready(format!("Match is {}", status), start_time, now)
+        }
+        Settlement::Cancel => ResolutionStatus::Resolved {
+            outcome: "cancelled".to_string(),
+            payouts: vec![U256::from(1); outcomes],
+        },
+        // Markets have at least two outcomes, so the home and away slots always exist
+        Settlement::Award(winner) => {
+            let qualifier = if winner == 0 { "home" } else { "away" };
+            let name = game.competitor(qualifier).map(|c| c.name.clone());
+            let mut payouts = vec![U256::ZERO; outcomes];
+            payouts[winner] = U256::from(1);
+            ResolutionStatus::Resolved {
+                outcome: name.unwrap_or_else(|| "awarded".to_string()),
+                payouts,
+            }
+        }
+    }
+}
+
+impl ResolutionStatus {
+    pub fn from_script(
+        result: Result<(String, Vec<U256>), ScriptError>,
+        start_time: u64,
+        now: u64,
+    ) -> Self {
+        match result {
+            Ok((outcome, payouts)) => ResolutionStatus::Resolved { outcome, payouts },
+            Err(ScriptError::Thrown(reason)) => Self::not_ready(reason, start_time, now),
+            Err(e @ (ScriptError::Rejected(_) | ScriptError::InvalidOutput(_))) => {
+                ResolutionStatus::Invalid { reason: e.to_string() }
+            }
+        }
+    }
+
+    /// Retries once the match is expected to be over, or after [`MIN_RETRY_SECS`] if it should
+    /// already be.
+    pub fn not_ready(reason: String, start_time: u64, now: u64) -> Self {
+        ResolutionStatus::NotReady {
+            reason,
+            retry_after: (start_time + MATCH_DURATION_SECS).max(now + MIN_RETRY_SECS),
+        }
+    }
+
+    /// Providers often catch up with each other within minutes, so markets they disagree on are
+    /// retried after [`MIN_RETRY_SECS`].
+    pub fn disagreement(reason: String, now: u64) -> Self {
+        ResolutionStatus::Disagreement { reason, retry_after: now + MIN_RETRY_SECS }
+    }
+}
+
+/// Scheduled start of the match in unix seconds, 0 if it can't be parsed.
+pub fn scheduled_start(game: &Game) -> u64 {
+    chrono::DateTime::parse_from_rfc3339(&game.start_time)
+        .ok()
+        .and_then(|time| u64::try_from(time.timestamp()).ok())
+        .unwrap_or_default()
+}
+
+pub fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
@@ -0,0 +1,50 @@
+//! Rhai resolution scripts, either built in or fetched from IPFS by CID.
+use cid::Cid;
+use sha2::{Digest, Sha256};
+use wavs_wasi_chain::http::{fetch_bytes, http_request_get};
+
+const DEFAULT_IPFS_GATEWAY: &str = "https://ipfs.io";
+/// Multicodec of raw blocks, whose content is the file itself
+const RAW_CODEC: u64 = 0x55;
+/// Multihash code of sha2-256
+const SHA2_256: u64 = 0x12;
+
+/// Outcomes of [`DEFAULT_SCRIPT`]: home, away and draw
+pub const DEFAULT_OUTCOMES: usize = 3;
+
+/// Match winner script used when the trigger carries no CID. Payouts are `[home, away, draw]`.
+pub const DEFAULT_SCRIPT: &str = r#"
+if !is_final() {
+    throw("Match has not ended yet");
+}
+
+if home_score > away_score {
+    #{ outcome: home_team, payout: [1, 0, 0] }
+} else if away_score > home_score {
+    #{ outcome: away_team, payout: [0, 1, 0] }
+} else {
+    #{ outcome: "draw", payout: [0, 0, 1] }
+}
+"#;
+
+/// Fetches a script through the `WAVS_ENV_IPFS_GATEWAY` gateway and checks it against its CID.
+///
+/// Only CIDv1 of raw blocks hashed with sha2-256 are accepted (e.g. `ipfs add --cid-version 1
+/// --raw-leaves`), so the content can be verified without decoding UnixFS.
+pub async fn fetch_script(cid: &str) -> Result<String, String> {
+    let parsed = Cid::try_from(cid).map_err(|e| format!("Invalid CID '{}': {}", cid, e))?;
+    if parsed.codec() != RAW_CODEC || parsed.hash().code() != SHA2_256 {
+        return Err(format!("CID '{}' must be a sha2-256 hashed raw block", cid));
+    }
+
+    let gateway =
+        std::env::var("WAVS_ENV_IPFS_GATEWAY").unwrap_or_else(|_| DEFAULT_IPFS_GATEWAY.to_string());
+    let url = format!("{}/ipfs/{}?format=raw", gateway.trim_end_matches('/'), cid);
+    let req = http_request_get(&url).map_err(|e| e.to_string())?;
+    let content = fetch_bytes(req).await.map_err(|e| e.to_string())?;
+
+    if Sha256::digest(&content).as_slice() != parsed.hash().digest() {
+        return Err(format!("Content fetched from {} does not match its CID", url));
+    }
+    String::from_utf8(content).map_err(|e| format!("Script is not valid UTF-8: {}", e))
+}
@@ -0,0 +1,155 @@
+//! Sport adapters, fetching a game from the Sportradar product of its sport and normalizing it
+//! into a [`Game`], so the same scripts work across sports.
+mod basketball;
+mod football;
+mod soccer;
+mod tennis;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::str::FromStr;
+use wavs_wasi_chain::http::{fetch_json, http_request_get};
+use wstd::http::HeaderValue;
+
+const SPORTRADAR_API: &str = "https://api.sportradar.com";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sport {
+    #[default]
+    Soccer,
+    /// NBA basketball
+    Nba,
+    /// NCAA men's basketball
+    Ncaamb,
+    Tennis,
+    /// NFL American football
+    Nfl,
+}
+
+impl FromStr for Sport {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "soccer" => Ok(Sport::Soccer),
+            "nba" => Ok(Sport::Nba),
+            "ncaamb" => Ok(Sport::Ncaamb),
+            "tennis" => Ok(Sport::Tennis),
+            "nfl" => Ok(Sport::Nfl),
+            other => Err(format!(
+                "Unknown sport '{}', expected soccer, nba, ncaamb, tennis or nfl",
+                other
+            )),
+        }
+    }
+}
+
+impl Sport {
+    pub async fn fetch_game(self, game_id: &str, api_key: &str) -> Result<Game, String> {
+        match self {
+            Sport::Soccer => soccer::fetch_game(game_id, api_key).await,
+            Sport::Nba => basketball::fetch_game(self, "nba", game_id, api_key).await,
+            Sport::Ncaamb => basketball::fetch_game(self, "ncaamb", game_id, api_key).await,
+            Sport::Tennis => tennis::fetch_game(game_id, api_key).await,
+            Sport::Nfl => football::fetch_game(game_id, api_key).await,
+        }
+    }
+}
+
+/// A game in the shape shared by every sport, exposed to scripts as `game`.
+#[derive(Debug, Serialize)]
+pub struct Game {
+    pub sport: Sport,
+    pub id: String,
+    /// Scheduled start, as an ISO-8601 time
+    pub start_time: String,
+    /// Status in the vocabulary of the soccer and tennis APIs (e.g. `not_started`, `live`,
+    /// `closed`), see [`crate::status::EventStatus`]
+    pub status: String,
+    /// Detail of the status, e.g. `halftime` or `walkover`
+    pub match_status: String,
+    pub home: Competitor,
+    pub away: Competitor,
+    pub winner_id: Option<String>,
+    /// Scores per period: halves, quarters or sets
+    pub periods: Vec<PeriodScore>,
+    /// Sport-specific fields, also exposed to scripts as variables
+    pub extras: Value,
+    /// The summary as returned by the sport's API
+    pub summary: Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Competitor {
+    pub id: String,
+    pub name: String,
+    pub abbreviation: String,
+    /// Goals, points, or sets won in tennis
+    pub score: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PeriodScore {
+    pub number: u32,
+    #[serde(rename = "type")]
+    pub period_type: String,
+    pub home_score: i64,
+    pub away_score: i64,
+}
+
+impl Game {
+    pub fn competitor(&self, qualifier: &str) -> Option<&Competitor> {
+        match qualifier {
+            "home" => Some(&self.home),
+            "away" => Some(&self.away),
+            _ => None,
+        }
+    }
+}
+
+/// Fetches a summary both as JSON, kept for scripts, and as the adapter's typed view of it.
+async fn fetch_summary<T: DeserializeOwned>(url: &str) -> Result<(Value, T), String> {
+    let mut req = http_request_get(url).map_err(|e| e.to_string())?;
+    req.headers_mut().insert("Accept", HeaderValue::from_static("application/json"));
+
+    let summary: Value = fetch_json(req).await.map_err(|e| e.to_string())?;
+    let typed = T::deserialize(&summary).map_err(|e| format!("Unexpected summary: {}", e))?;
+    Ok((summary, typed))
+}
+
+/// Maps the game statuses of the US sports APIs (NBA, NCAA, NFL) to [`Game::status`], keeping
+/// the original as `match_status`.
+fn us_status(status: &str) -> (String, String) {
+    let normalized = match status {
+        "scheduled" | "created" | "time-tbd" | "if-necessary" | "flex-schedule" => "not_started",
+        "inprogress" | "halftime" => "live",
+        "complete" => "ended",
+        "unnecessary" => "cancelled",
+        // closed, cancelled, postponed, delayed and suspended are shared
+        other => other,
+    };
+    (normalized.to_string(), status.to_string())
+}
+
+/// Name of a US team, prefixed with its market (city or school) when there is one.
+fn full_name(market: &str, name: &str) -> String {
+    match market.is_empty() {
+        true => name.to_string(),
+        false => format!("{} {}", market, name),
+    }
+}
+
+/// Winner of a final game of a sport without draws reported by the API, `None` on a tie.
+pub(crate) fn winner_by_score(
+    status: &str,
+    home: &Competitor,
+    away: &Competitor,
+) -> Option<String> {
+    if !matches!(status, "ended" | "closed") || home.score == away.score {
+        return None;
+    }
+    let winner = if home.score > away.score { home } else { away };
+    Some(winner.id.clone())
+}
@@ -0,0 +1,101 @@
+//! NBA and NCAA men's basketball v8 game summaries.
+use super::{
+    fetch_summary, full_name, us_status, winner_by_score, Competitor, Game, PeriodScore, Sport,
+    SPORTRADAR_API,
+};
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Debug, Deserialize)]
+struct GameSummary {
+    id: String,
+    status: String,
+    scheduled: String,
+    #[serde(default)]
+    neutral_site: bool,
+    home: Team,
+    away: Team,
+}
+
+#[derive(Debug, Deserialize)]
+struct Team {
+    id: String,
+    name: String,
+    /// School of NCAA teams, e.g. `Duke` for the Blue Devils
+    #[serde(default)]
+    market: String,
+    #[serde(default)]
+    alias: String,
+    #[serde(default)]
+    points: i64,
+    /// Points per quarter (NBA) or half (NCAA), and per overtime
+    #[serde(default)]
+    scoring: Vec<Scoring>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Scoring {
+    #[serde(rename = "type")]
+    period_type: String,
+    number: u32,
+    points: i64,
+}
+
+impl Team {
+    fn competitor(&self) -> Competitor {
+        Competitor {
+            id: self.id.clone(),
+            name: full_name(&self.market, &self.name),
+            abbreviation: self.alias.clone(),
+            score: self.points,
+        }
+    }
+}
+
+/// `league` is the path of the Sportradar product, `nba` or `ncaamb`.
+pub async fn fetch_game(
+    sport: Sport,
+    league: &str,
+    game_id: &str,
+    api_key: &str,
+) -> Result<Game, String> {
+    let url = format!(
+        "{}/{}/trial/v8/en/games/{}/summary.json?api_key={}",
+        SPORTRADAR_API, league, game_id, api_key
+    );
+    let (summary, data) = fetch_summary::<GameSummary>(&url).await?;
+
+    let (status, match_status) = us_status(&data.status);
+    let (home, away) = (data.home.competitor(), data.away.competitor());
+    // Periods are listed in the same order by both teams
+    let periods: Vec<PeriodScore> = data
+        .home
+        .scoring
+        .iter()
+        .zip(&data.away.scoring)
+        .map(|(home, away)| PeriodScore {
+            number: home.number,
+            period_type: home.period_type.clone(),
+            home_score: home.points,
+            away_score: away.points,
+        })
+        .collect();
+    let extras = json!({
+        "overtime": periods.iter().any(|p| p.period_type == "overtime"),
+        "neutral_site": data.neutral_site,
+    });
+
+    Ok(Game {
+        sport,
+        id: data.id,
+        start_time: data.scheduled,
+        winner_id: winner_by_score(&status, &home, &away),
+        status,
+        match_status,
+        home,
+        away,
+        periods,
+        extras,
+        summary,
+    })
+}
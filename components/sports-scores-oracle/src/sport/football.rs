@@ -0,0 +1,108 @@
+//! NFL v7 game boxscores.
+use super::{
+    fetch_summary, full_name, us_status, winner_by_score, Competitor, Game, PeriodScore, Sport,
+    SPORTRADAR_API,
+};
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Debug, Deserialize)]
+struct Boxscore {
+    id: String,
+    status: String,
+    scheduled: String,
+    summary: BoxscoreSummary,
+    scoring: Option<Scoring>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BoxscoreSummary {
+    week: Option<Week>,
+    home: Team,
+    away: Team,
+}
+
+#[derive(Debug, Deserialize)]
+struct Week {
+    sequence: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct Team {
+    id: String,
+    name: String,
+    /// City of the team, e.g. `Kansas City` for the Chiefs
+    #[serde(default)]
+    market: String,
+    #[serde(default)]
+    alias: String,
+    #[serde(default)]
+    points: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Scoring {
+    #[serde(default)]
+    periods: Vec<Period>,
+}
+
+/// Points of both teams in a quarter or overtime
+#[derive(Debug, Deserialize)]
+struct Period {
+    period_type: String,
+    number: u32,
+    home_points: i64,
+    away_points: i64,
+}
+
+impl Team {
+    fn competitor(&self) -> Competitor {
+        Competitor {
+            id: self.id.clone(),
+            name: full_name(&self.market, &self.name),
+            abbreviation: self.alias.clone(),
+            score: self.points,
+        }
+    }
+}
+
+pub async fn fetch_game(game_id: &str, api_key: &str) -> Result<Game, String> {
+    let url = format!(
+        "{}/nfl/official/trial/v7/en/games/{}/boxscore.json?api_key={}",
+        SPORTRADAR_API, game_id, api_key
+    );
+    let (summary, data) = fetch_summary::<Boxscore>(&url).await?;
+
+    let (status, match_status) = us_status(&data.status);
+    let (home, away) = (data.summary.home.competitor(), data.summary.away.competitor());
+    let periods: Vec<PeriodScore> = data
+        .scoring
+        .map(|scoring| scoring.periods)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|p| PeriodScore {
+            number: p.number,
+            period_type: p.period_type,
+            home_score: p.home_points,
+            away_score: p.away_points,
+        })
+        .collect();
+    let extras = json!({
+        "week": data.summary.week.map(|week| week.sequence),
+        "overtime": periods.iter().any(|p| p.period_type == "overtime"),
+    });
+
+    Ok(Game {
+        sport: Sport::Nfl,
+        id: data.id,
+        start_time: data.scheduled,
+        winner_id: winner_by_score(&status, &home, &away),
+        status,
+        match_status,
+        home,
+        away,
+        periods,
+        extras,
+        summary,
+    })
+}
@@ -0,0 +1,237 @@
+//! Soccer v4 summaries, e.g. `sr:sport_event:50850237`.
+use super::{
+    fetch_summary, Competitor as GameCompetitor, Game, PeriodScore as GamePeriodScore, Sport,
+    SPORTRADAR_API,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+pub async fn fetch_game(game_id: &str, api_key: &str) -> Result<Game, String> {
+    let url = format!(
+        "{}/soccer/trial/v4/en/sport_events/{}/summary.json?api_key={}",
+        SPORTRADAR_API, game_id, api_key
+    );
+    let (summary, data) = fetch_summary::<MatchResult>(&url).await?;
+    let status = &data.sport_event_status;
+    let event = &data.sport_event;
+    let context = &event.sport_event_context;
+
+    let competitor = |qualifier: &str, score: i32| {
+        let team = event.team_by_qualifier(qualifier);
+        GameCompetitor {
+            id: team.map(|t| t.id.clone()).unwrap_or_default(),
+            name: team.map(|t| t.name.clone()).unwrap_or_default(),
+            abbreviation: team.map(|t| t.abbreviation.clone()).unwrap_or_default(),
+            score: score.into(),
+        }
+    };
+    let conditions = event.sport_event_conditions.as_ref();
+    let weather = conditions.and_then(|c| c.weather.as_ref());
+    let extras = json!({
+        "confirmed": event.start_time_confirmed,
+        "competition": context.competition.name,
+        "competition_id": context.competition.id,
+        "season": context.season.name,
+        "stage": context.stage.phase,
+        "category": context.category.name,
+        "country_code": context.category.country_code,
+        "round_number": context.round.as_ref().map(|round| round.number),
+        "group": context.groups.first().map(|group| &group.group_name),
+        "pitch_conditions": weather.map(|w| &w.pitch_conditions),
+        "overall_conditions": weather.map(|w| &w.overall_conditions),
+        "neutral_ground": conditions.and_then(|c| c.ground.as_ref()).map(|g| g.neutral),
+    });
+
+    Ok(Game {
+        sport: Sport::Soccer,
+        id: event.id.clone(),
+        start_time: event.start_time.clone(),
+        status: status.status.clone(),
+        match_status: status.match_status.clone(),
+        home: competitor("home", status.home_score),
+        away: competitor("away", status.away_score),
+        winner_id: status.winner_id.clone(),
+        periods: status
+            .period_scores
+            .iter()
+            .map(|p| GamePeriodScore {
+                number: p.number.into(),
+                period_type: p.period_type.clone(),
+                home_score: p.home_score.into(),
+                away_score: p.away_score.into(),
+            })
+            .collect(),
+        extras,
+        summary,
+    })
+}
+
+// chatgpt structures for soccer api
+impl SportEvent {
+    pub fn team_by_qualifier(&self, role: &str) -> Option<&Competitor> {
+        self.competitors.iter().find(|c| c.qualifier == role)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MatchResult {
+    pub generated_at: String,
+    pub sport_event: SportEvent,
+    pub sport_event_status: SportEventStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SportEvent {
+    pub id: String,
+    pub start_time: String,
+    pub start_time_confirmed: bool,
+    pub sport_event_context: SportEventContext,
+    pub coverage: Coverage,
+    pub competitors: Vec<Competitor>,
+    pub sport_event_conditions: Option<SportEventConditions>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SportEventContext {
+    pub sport: NamedEntity,
+    pub category: Category,
+    pub competition: Competition,
+    pub season: Season,
+    pub stage: Stage,
+    pub round: Option<Round>,
+    pub groups: Vec<Group>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NamedEntity {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Category {
+    pub id: String,
+    pub name: String,
+    pub country_code: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Competition {
+    pub id: String,
+    pub name: String,
+    pub gender: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Season {
+    pub id: String,
+    pub name: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub year: String,
+    pub competition_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Stage {
+    pub order: u32,
+    #[serde(rename = "type")]
+    pub stage_type: String,
+    pub phase: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub year: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Round {
+    pub number: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Group {
+    pub id: String,
+    pub name: String,
+    pub group_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Coverage {
+    #[serde(rename = "type")]
+    pub coverage_type: String,
+    pub sport_event_properties: CoverageProperties,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CoverageProperties {
+    pub lineups: bool,
+    pub formations: bool,
+    pub venue: bool,
+    pub extended_player_stats: bool,
+    pub extended_team_stats: bool,
+    pub ballspotting: bool,
+    pub commentary: bool,
+    pub fun_facts: bool,
+    pub goal_scorers: bool,
+    pub goal_scorers_live: bool,
+    pub scores: String,
+    pub game_clock: bool,
+    pub deeper_play_by_play: bool,
+    pub deeper_player_stats: bool,
+    pub deeper_team_stats: bool,
+    pub basic_play_by_play: bool,
+    pub basic_player_stats: bool,
+    pub basic_team_stats: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Competitor {
+    pub id: String,
+    pub name: String,
+    pub country: String,
+    pub country_code: String,
+    pub abbreviation: String,
+    pub qualifier: String,
+    pub gender: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SportEventConditions {
+    pub weather: Option<Weather>,
+    pub ground: Option<Ground>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Weather {
+    pub pitch_conditions: String,
+    pub overall_conditions: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Ground {
+    pub neutral: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SportEventStatus {
+    pub status: String,
+    #[serde(default)]
+    pub match_status: String,
+    /// Missing until the match starts, and for postponed or cancelled matches
+    #[serde(default)]
+    pub home_score: i32,
+    #[serde(default)]
+    pub away_score: i32,
+    pub winner_id: Option<String>,
+    #[serde(default)]
+    pub period_scores: Vec<PeriodScore>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PeriodScore {
+    pub home_score: i32,
+    pub away_score: i32,
+    #[serde(rename = "type")]
+    pub period_type: String,
+    pub number: u8,
+}
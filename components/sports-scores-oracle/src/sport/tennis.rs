@@ -0,0 +1,117 @@
+//! Tennis v3 summaries. Scores are sets won, and periods the games of each set.
+use super::{fetch_summary, Competitor, Game, PeriodScore, Sport, SPORTRADAR_API};
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Debug, Deserialize)]
+struct MatchSummary {
+    sport_event: SportEvent,
+    sport_event_status: SportEventStatus,
+}
+
+#[derive(Debug, Deserialize)]
+struct SportEvent {
+    id: String,
+    start_time: String,
+    sport_event_context: Option<SportEventContext>,
+    competitors: Vec<Player>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SportEventContext {
+    competition: Option<Named>,
+    round: Option<Named>,
+    mode: Option<Mode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Named {
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Mode {
+    best_of: Option<u32>,
+}
+
+/// A player, or a pair in doubles
+#[derive(Debug, Deserialize)]
+struct Player {
+    id: String,
+    name: String,
+    #[serde(default)]
+    abbreviation: String,
+    qualifier: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SportEventStatus {
+    status: String,
+    #[serde(default)]
+    match_status: String,
+    #[serde(default)]
+    home_score: i64,
+    #[serde(default)]
+    away_score: i64,
+    winner_id: Option<String>,
+    #[serde(default)]
+    period_scores: Vec<SetScore>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SetScore {
+    number: u32,
+    #[serde(rename = "type")]
+    period_type: String,
+    home_score: i64,
+    away_score: i64,
+}
+
+pub async fn fetch_game(game_id: &str, api_key: &str) -> Result<Game, String> {
+    let url = format!(
+        "{}/tennis/trial/v3/en/sport_events/{}/summary.json?api_key={}",
+        SPORTRADAR_API, game_id, api_key
+    );
+    let (summary, data) = fetch_summary::<MatchSummary>(&url).await?;
+    let event = data.sport_event;
+    let status = data.sport_event_status;
+
+    let competitor = |qualifier: &str, score: i64| {
+        let player = event.competitors.iter().find(|p| p.qualifier == qualifier);
+        Competitor {
+            id: player.map(|p| p.id.clone()).unwrap_or_default(),
+            name: player.map(|p| p.name.clone()).unwrap_or_default(),
+            abbreviation: player.map(|p| p.abbreviation.clone()).unwrap_or_default(),
+            score,
+        }
+    };
+    let context = event.sport_event_context.as_ref();
+    let extras = json!({
+        "competition": context.and_then(|c| c.competition.as_ref()).and_then(|c| c.name.clone()),
+        "round": context.and_then(|c| c.round.as_ref()).and_then(|r| r.name.clone()),
+        "best_of": context.and_then(|c| c.mode.as_ref()).and_then(|m| m.best_of),
+    });
+
+    Ok(Game {
+        sport: Sport::Tennis,
+        home: competitor("home", status.home_score),
+        away: competitor("away", status.away_score),
+        id: event.id,
+        start_time: event.start_time,
+        status: status.status,
+        match_status: status.match_status,
+        winner_id: status.winner_id,
+        periods: status
+            .period_scores
+            .into_iter()
+            .map(|set| PeriodScore {
+                number: set.number,
+                period_type: set.period_type,
+                home_score: set.home_score,
+                away_score: set.away_score,
+            })
+            .collect(),
+        extras,
+        summary,
+    })
+}
@@ -0,0 +1,113 @@
+//! Sportradar match statuses, and how each settles a market before its script runs.
+use crate::sport::Game;
+
+/// The `status` of a sport event, with walkovers told apart through `match_status`. Tennis matches
+/// won by retirement or default are walkovers too, settled from their winner rather than the score.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventStatus {
+    NotStarted,
+    MatchAboutToStart,
+    Live,
+    Delayed,
+    Interrupted,
+    Suspended,
+    Postponed,
+    Cancelled,
+    Abandoned,
+    Walkover,
+    Ended,
+    Closed,
+    /// A status this component doesn't know, left to the script
+    Other(String),
+}
+
+/// How a market is settled given the status of its match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Settlement {
+    /// The match was played (or hasn't been), the market's script decides
+    Script,
+    /// The match won't be played to a result, every outcome is refunded
+    Cancel,
+    /// The competitor at this index (home 0, away 1) was awarded the win
+    Award(usize),
+    /// The match is paused and should be resolved later
+    Retry,
+}
+
+/// Coarse stage of a match, comparable between providers reporting statuses at different
+/// granularities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Not started, in play or paused
+    Pending,
+    Final,
+    /// Postponed, cancelled or abandoned
+    CalledOff,
+    Walkover,
+}
+
+impl Game {
+    pub fn event_status(&self) -> EventStatus {
+        if matches!(self.match_status.as_str(), "walkover" | "retired" | "defaulted") {
+            return EventStatus::Walkover;
+        }
+        match self.status.as_str() {
+            "not_started" => EventStatus::NotStarted,
+            "match_about_to_start" => EventStatus::MatchAboutToStart,
+            "live" | "started" => EventStatus::Live,
+            "delayed" => EventStatus::Delayed,
+            "interrupted" => EventStatus::Interrupted,
+            "suspended" => EventStatus::Suspended,
+            "postponed" => EventStatus::Postponed,
+            "cancelled" => EventStatus::Cancelled,
+            "abandoned" => EventStatus::Abandoned,
+            "ended" => EventStatus::Ended,
+            "closed" => EventStatus::Closed,
+            other => EventStatus::Other(other.to_string()),
+        }
+    }
+
+    /// Index of the winner of the match, home 0 and away 1
+    pub fn awarded_index(&self) -> Option<usize> {
+        match self.winner_id.as_ref()? {
+            id if *id == self.home.id => Some(0),
+            id if *id == self.away.id => Some(1),
+            _ => None,
+        }
+    }
+}
+
+impl EventStatus {
+    /// Whether the match was played to the end
+    pub fn is_final(&self) -> bool {
+        matches!(self, EventStatus::Ended | EventStatus::Closed)
+    }
+
+    /// Whether the match was called off rather than played to the end
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, EventStatus::Postponed | EventStatus::Cancelled | EventStatus::Abandoned)
+    }
+
+    pub fn phase(&self) -> Phase {
+        match self {
+            _ if self.is_final() => Phase::Final,
+            _ if self.is_cancelled() => Phase::CalledOff,
+            EventStatus::Walkover => Phase::Walkover,
+            _ => Phase::Pending,
+        }
+    }
+
+    /// Postponed and cancelled matches are refunded. Walkovers and abandoned matches go to the
+    /// competitor awarded the win, and are refunded when there is none. Interrupted and suspended
+    /// matches are retried.
+    pub fn settlement(&self, game: &Game) -> Settlement {
+        match self {
+            EventStatus::Postponed | EventStatus::Cancelled => Settlement::Cancel,
+            EventStatus::Abandoned | EventStatus::Walkover => {
+                game.awarded_index().map_or(Settlement::Cancel, Settlement::Award)
+            }
+            EventStatus::Interrupted | EventStatus::Suspended => Settlement::Retry,
+            _ => Settlement::Script,
+        }
+    }
+}
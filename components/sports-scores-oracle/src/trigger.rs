@@ -1,4 +1,5 @@
 use crate::bindings::wavs::worker::layer_types::{TriggerData, TriggerDataEthContractEvent};
+use crate::resolution::{Resolution, ResolutionStatus};
 use alloy_sol_types::SolValue;
 use anyhow::Result;
 use wavs_wasi_chain::decode_event_log_data;
@@ -15,20 +16,38 @@ pub fn decode_trigger_event(trigger_data: TriggerData) -> Result<(u64, Vec<u8>,
             let trigger_info = solidity::TriggerInfo::abi_decode(&event._triggerInfo, false)?;
             Ok((trigger_info.triggerId, trigger_info.data.to_vec(), Destination::Ethereum))
         }
-        TriggerData::Raw(data) => {
-            // For CLI output, split by | to extract game ID and API key
-            let data_str =
-                std::str::from_utf8(&data).map_err(|e| anyhow::anyhow!("Invalid UTF-8: {}", e))?;
-
-            Ok((0, data.clone(), Destination::CliOutput))
-        }
+        TriggerData::Raw(data) => Ok((0, data.clone(), Destination::CliOutput)),
         _ => Err(anyhow::anyhow!("Unsupported trigger data type")),
     }
 }
 
-pub fn encode_trigger_output(trigger_id: u64, output: impl AsRef<[u8]>) -> Vec<u8> {
-    solidity::DataWithId { triggerId: trigger_id, data: output.as_ref().to_vec().into() }
-        .abi_encode()
+/// Encodes the resolution as the `AvsOutputData` handled by the prediction market controller,
+/// wrapped in a `DataWithId`. Only resolved markets carry payouts for
+/// `ConditionalTokens.reportPayouts`.
+pub fn encode_trigger_output(trigger_id: u64, resolution: &Resolution) -> Vec<u8> {
+    let (status, payouts, retry_after, reason) = match &resolution.status {
+        ResolutionStatus::Resolved { payouts, .. } => {
+            (solidity::ResolutionStatus::Resolved, payouts.clone(), 0, String::new())
+        }
+        ResolutionStatus::NotReady { reason, retry_after } => {
+            (solidity::ResolutionStatus::NotReady, Vec::new(), *retry_after, reason.clone())
+        }
+        ResolutionStatus::Invalid { reason } => {
+            (solidity::ResolutionStatus::Invalid, Vec::new(), 0, reason.clone())
+        }
+        ResolutionStatus::Disagreement { reason, retry_after } => {
+            (solidity::ResolutionStatus::Disagreement, Vec::new(), *retry_after, reason.clone())
+        }
+    };
+    let output = solidity::AvsOutputData {
+        status,
+        questionId: resolution.question_id,
+        payouts,
+        startTime: resolution.start_time,
+        retryAfter: retry_after,
+        reason,
+    };
+    solidity::DataWithId { triggerId: trigger_id, data: output.abi_encode().into() }.abi_encode()
 }
 
 mod solidity {
@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: MIT
+pragma solidity 0.8.22;
+
+import {MockToken, MockUniswapV2Pair, MockUniswapV3Pool} from "contracts/mocks/MockDexPools.sol";
+import {Common} from "script/Common.s.sol";
+import {console} from "forge-std/console.sol";
+
+/// @dev Script to deploy WETH/USDC mock pools priced at 3000 USDC for the dex price source
+contract DeployMockPools is Common {
+    /// @dev sqrt(3000 * 10 ** 6 / 10 ** 18) * 2 ** 96
+    uint160 internal constant SQRT_PRICE_X96 = 4339505179874779489431521;
+
+    function run() public {
+        vm.startBroadcast(_privateKey);
+        MockToken weth = new MockToken("WETH", 18);
+        MockToken usdc = new MockToken("USDC", 6);
+
+        MockUniswapV2Pair pair = new MockUniswapV2Pair(address(weth), address(usdc));
+        pair.setReserves(1_000 ether, 3_000_000e6);
+
+        MockUniswapV3Pool pool = new MockUniswapV3Pool(address(weth), address(usdc));
+        pool.setSqrtPriceX96(SQRT_PRICE_X96);
+        vm.stopBroadcast();
+
+        console.log("V2 pair", address(pair));
+        console.log("V3 pool", address(pool));
+    }
+}
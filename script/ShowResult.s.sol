@@ -4,12 +4,19 @@ pragma solidity 0.8.22;
 import {SimpleTrigger} from "contracts/WavsTrigger.sol";
 import {SimpleSubmit} from "contracts/WavsSubmit.sol";
 import {ITypes} from "interfaces/ITypes.sol";
+import {IPriceFeed} from "interfaces/IPriceFeed.sol";
 import {Common} from "script/Common.s.sol";
 import {console} from "forge-std/console.sol";
 
 /// @dev Script to show the result of a trigger
 contract ShowResult is Common {
+    /// @dev Shows the raw result, whatever the component that produced it
     function run(string calldata serviceTriggerAddr, string calldata serviceHandlerAddr) public {
+        run(serviceTriggerAddr, serviceHandlerAddr, "");
+    }
+
+    /// @dev Shows the result decoded for the price oracle `mode` of the trigger, or raw for other modes
+    function run(string calldata serviceTriggerAddr, string calldata serviceHandlerAddr, string memory mode) public {
         vm.startBroadcast(_privateKey);
         SimpleTrigger trigger = SimpleTrigger(vm.parseAddress(serviceTriggerAddr));
         SimpleSubmit submit = SimpleSubmit(vm.parseAddress(serviceHandlerAddr));
@@ -18,8 +25,109 @@ contract ShowResult is Common {
         console.log("Fetching data for TriggerId", ITypes.TriggerId.unwrap(triggerId));
 
         bytes memory data = submit.getData(triggerId);
-        console.log("Data:", string(data));
+        bytes32 selected = keccak256(bytes(mode));
+        if (selected == keccak256("spot")) {
+            _showPrices(abi.decode(data, (IPriceFeed.PriceData[])));
+        } else if (selected == keccak256("twap")) {
+            _showTwaps(abi.decode(data, (IPriceFeed.TwapData[])));
+        } else if (selected == keccak256("aggregator_v3")) {
+            _showRound(abi.decode(data, (IPriceFeed.RoundData)));
+        } else if (selected == keccak256("historical")) {
+            _showHistorical(abi.decode(data, (IPriceFeed.HistoricalPriceData[])));
+        } else if (selected == keccak256("stats")) {
+            _showStats(abi.decode(data, (IPriceFeed.MarketStatsData[])));
+        } else if (selected == keccak256("volatility")) {
+            _showVolatilities(abi.decode(data, (IPriceFeed.VolatilityData[])));
+        } else if (selected == keccak256("resolve")) {
+            _showResolution(abi.decode(data, (ITypes.AvsOutputData)));
+        } else {
+            console.log("Data:", string(data));
+        }
 
         vm.stopBroadcast();
     }
+
+    function _showPrices(IPriceFeed.PriceData[] memory prices) internal pure {
+        for (uint256 i = 0; i < prices.length; i++) {
+            console.log("Id:", uint256(prices[i].id));
+            console.log("Symbol:", prices[i].symbol);
+            console.log("Currency:", prices[i].currency);
+            console.log("Price:", prices[i].price);
+            console.log("Confidence:", prices[i].conf);
+            console.log("Decimals:", uint256(prices[i].decimals));
+            console.log("Timestamp:", uint256(prices[i].timestamp));
+            console.log("Sources:", uint256(prices[i].sources));
+            console.log("Confidence method:", uint256(prices[i].confMethod));
+            console.log("Derived:", prices[i].derived);
+        }
+    }
+
+    function _showTwaps(IPriceFeed.TwapData[] memory twaps) internal pure {
+        for (uint256 i = 0; i < twaps.length; i++) {
+            console.log("Id:", uint256(twaps[i].id));
+            console.log("Symbol:", twaps[i].symbol);
+            console.log("Price:", twaps[i].price);
+            console.log("Decimals:", uint256(twaps[i].decimals));
+            console.log("Samples:", uint256(twaps[i].samples));
+            console.log("Window start:", uint256(twaps[i].windowStart));
+            console.log("Window end:", uint256(twaps[i].windowEnd));
+        }
+    }
+
+    function _showRound(IPriceFeed.RoundData memory round) internal pure {
+        console.log("Round:", uint256(round.roundId));
+        console.log("Answer:", round.answer);
+        console.log("Started at:", round.startedAt);
+        console.log("Updated at:", round.updatedAt);
+        console.log("Answered in round:", uint256(round.answeredInRound));
+    }
+
+    function _showHistorical(IPriceFeed.HistoricalPriceData[] memory prices) internal pure {
+        for (uint256 i = 0; i < prices.length; i++) {
+            console.log("Id:", uint256(prices[i].id));
+            console.log("Symbol:", prices[i].symbol);
+            console.log("Price:", prices[i].price);
+            console.log("Decimals:", uint256(prices[i].decimals));
+            console.log("Target timestamp:", uint256(prices[i].targetTimestamp));
+            console.log("Sample timestamp:", uint256(prices[i].sampleTimestamp));
+            console.log("Distance:", uint256(prices[i].distance));
+        }
+    }
+
+    function _showStats(IPriceFeed.MarketStatsData[] memory stats) internal pure {
+        for (uint256 i = 0; i < stats.length; i++) {
+            console.log("Id:", uint256(stats[i].id));
+            console.log("Symbol:", stats[i].symbol);
+            console.log("Timestamp:", uint256(stats[i].timestamp));
+            console.log("Decimals:", uint256(stats[i].decimals));
+            console.log("Fields:", uint256(stats[i].fields));
+            console.log("Price:", stats[i].price);
+            console.log("Market cap:", stats[i].marketCap);
+            console.log("Volume 24h:", stats[i].volume24h);
+            console.log("Circulating supply:", stats[i].circulatingSupply);
+            console.log("Total supply:", stats[i].totalSupply);
+            console.log("Change 24h (bps):", int256(stats[i].change24hBps));
+        }
+    }
+
+    function _showVolatilities(IPriceFeed.VolatilityData[] memory volatilities) internal pure {
+        for (uint256 i = 0; i < volatilities.length; i++) {
+            console.log("Id:", uint256(volatilities[i].id));
+            console.log("Symbol:", volatilities[i].symbol);
+            console.log("Volatility:", volatilities[i].volatility);
+            console.log("Decimals:", uint256(volatilities[i].decimals));
+            console.log("Samples:", uint256(volatilities[i].samples));
+            console.log("Window start:", uint256(volatilities[i].windowStart));
+            console.log("Window end:", uint256(volatilities[i].windowEnd));
+        }
+    }
+
+    function _showResolution(ITypes.AvsOutputData memory resolution) internal pure {
+        console.log("Status:", uint256(resolution.status));
+        console.logBytes32(resolution.questionId);
+        for (uint256 i = 0; i < resolution.payouts.length; i++) {
+            console.log("Payout:", resolution.payouts[i]);
+        }
+        console.log("Reason:", resolution.reason);
+    }
 }
@@ -5,42 +5,65 @@ pragma solidity ^0.8.22;
 
 import {IWavsServiceHandler} from "@wavs/interfaces/IWavsServiceHandler.sol";
 import {IWavsServiceManager} from "@wavs/interfaces/IWavsServiceManager.sol";
-import {IWavsTrigger} from "interfaces/IWavsTrigger.sol";
-import {ConditionalTokens} from "../../../fpmm_using_uniswap/src/CT.sol";
-//import {ConditionalTokens} from "@lay3rlabs/conditional-tokens-contracts/ConditionalTokens.sol";
+import {ITypes} from "interfaces/ITypes.sol";
+import {IConditionalTokens} from "interfaces/IConditionalTokens.sol";
+import {Strings} from "@openzeppelin-contracts/utils/Strings.sol";
 //import {LMSRMarketMaker} from "@lay3rlabs/conditional-tokens-market-makers/LMSRMarketMaker.sol";
 //import {PredictionMarketFactory} from "./PredictionMarketFactory.sol";
 
 // The contract responsible for triggering the oracle to resolve the market and handling the oracle output and instructing the market maker to resolve the market.
-contract PredictionMarketOracleController is IWavsTrigger, IWavsServiceHandler {
-    // The factory that handles creating and resolving the market.
-    ConditionalTokens public ct;
+contract PredictionMarketOracleController is ITypes, IWavsServiceHandler {
+    /// @notice A request to the oracle AVS and the account that made it
+    struct Trigger {
+        address creator;
+        bytes data;
+    }
+
+    // The conditional tokens the market's payouts are reported to.
+    IConditionalTokens public ct;
 
-    /// @inheritdoc IWavsTrigger
+    /// @notice Triggers by id
     mapping(TriggerId _triggerId => Trigger _trigger) public triggersById;
 
     IWavsServiceManager public serviceManager;
     TriggerId public nextTriggerId;
 
+    /// @notice Account allowed to create markets, and so to choose their question, script and outcomes
+    address public owner;
+
+    /// @notice Question each trigger resolves, the only one its output may report payouts for
+    mapping(TriggerId _triggerId => bytes32 _questionId) public questionIdById;
+
+    /// @notice Time after which a trigger reported as not ready can be re-armed, 0 when not waiting
+    mapping(TriggerId _triggerId => uint64 _retryAfter) public retryAfterById;
+
+    /// @notice Emitted when the AVS reports a market that can't be resolved yet
+    event ResolutionDeferred(
+        TriggerId indexed triggerId, bytes32 indexed questionId, uint64 startTime, uint64 retryAfter, string reason
+    );
+
+    /// @notice Emitted when the AVS reports a market that can't be resolved from its script and data
+    event ResolutionInvalid(TriggerId indexed triggerId, bytes32 indexed questionId, string reason);
+
+    /// @notice Emitted when the data providers of the AVS disagree on the result of a market
+    event ResolutionDisagreement(
+        TriggerId indexed triggerId, bytes32 indexed questionId, uint64 retryAfter, string reason
+    );
+
     constructor(address serviceManager_, address hook) {
         require(serviceManager_ != address(0), "Invalid service manager");
 
-        ct = ConditionalTokens(hook);
+        ct = IConditionalTokens(hook);
         serviceManager = IWavsServiceManager(serviceManager_);
+        owner = msg.sender;
     }
 
-    /**
-     * @param envelope The envelope containing the data.
-     * @param signatureData The signature data.
-     */
-    function handleSignedEnvelope(
-        Envelope calldata envelope,
-        SignatureData calldata signatureData
-    ) external override {
-        serviceManager.validate(envelope, signatureData);
+    /// @inheritdoc IWavsServiceHandler
+    function handleSignedData(bytes calldata _data, bytes calldata _signature) external {
+        serviceManager.validate(_data, _signature);
 
         DataWithId memory dataWithId = abi.decode(
-            envelope.payload,
+            _data,
             (DataWithId)
         );
 
@@ -51,8 +74,28 @@ contract PredictionMarketOracleController is IWavsTrigger, IWavsServiceHandler {
             dataWithId.data,
             (AvsOutputData)
         );
-
-        ct.reportPayouts(returnData.questionId, returnData.payouts);
+        require(returnData.questionId == questionIdById[dataWithId.triggerId], "Question does not match trigger");
+
+        if (returnData.status == ResolutionStatus.Resolved) {
+            delete retryAfterById[dataWithId.triggerId];
+            ct.reportPayouts(returnData.questionId, returnData.payouts);
+        } else if (returnData.status == ResolutionStatus.NotReady) {
+            retryAfterById[dataWithId.triggerId] = returnData.retryAfter;
+            emit ResolutionDeferred(
+                dataWithId.triggerId,
+                returnData.questionId,
+                returnData.startTime,
+                returnData.retryAfter,
+                returnData.reason
+            );
+        } else if (returnData.status == ResolutionStatus.Disagreement) {
+            retryAfterById[dataWithId.triggerId] = returnData.retryAfter;
+            emit ResolutionDisagreement(
+                dataWithId.triggerId, returnData.questionId, returnData.retryAfter, returnData.reason
+            );
+        } else {
+            emit ResolutionInvalid(dataWithId.triggerId, returnData.questionId, returnData.reason);
+        }
 
         // Tell factory to resolve the market
         //factory.resolveMarket(
@@ -64,10 +107,12 @@ contract PredictionMarketOracleController is IWavsTrigger, IWavsServiceHandler {
 
     /**
      * @dev Trigger the oracle AVS to resolve the market.
+     * @param questionId The question the market's payouts are reported for.
+     * @param data The request, `GAME_ID|API_KEY[|key=value...]` without the `question` option, which is appended from `questionId`.
      * @return triggerId The ID of the trigger.
      */
-     // TODO: add condition as input?
-    function addTrigger(bytes32 condition) external payable returns (TriggerId triggerId) {
+    function addTrigger(bytes32 questionId, bytes calldata data) external payable returns (TriggerId triggerId) {
+        require(msg.sender == owner, "Only the owner can add triggers");
         //require(msg.value == 0.1 ether, "Payment must be exactly 0.1 ETH");
 
         // Get the next trigger ID
@@ -76,9 +121,10 @@ contract PredictionMarketOracleController is IWavsTrigger, IWavsServiceHandler {
 
         Trigger memory trigger = Trigger({
             creator: msg.sender,
-            data: condition //bytes("")
+            data: abi.encodePacked(data, "|question=", Strings.toHexString(uint256(questionId), 32))
         });
         triggersById[triggerId] = trigger;
+        questionIdById[triggerId] = questionId;
 
         TriggerInfo memory triggerInfo = TriggerInfo({
             triggerId: triggerId,
@@ -89,10 +135,34 @@ contract PredictionMarketOracleController is IWavsTrigger, IWavsServiceHandler {
         emit NewTrigger(abi.encode(triggerInfo));
     }
 
-    /// @inheritdoc IWavsTrigger
+    /**
+     * @dev Trigger the oracle AVS again for a market it reported as not ready or disputed, once its retry time has passed.
+     * @param triggerId The ID of the trigger to re-arm.
+     */
+    function rearmTrigger(TriggerId triggerId) external {
+        uint64 retryAfter = retryAfterById[triggerId];
+        require(retryAfter != 0, "Trigger is not waiting for a retry");
+        require(block.timestamp >= retryAfter, "Too early to retry");
+        delete retryAfterById[triggerId];
+
+        Trigger storage trigger = triggersById[triggerId];
+        TriggerInfo memory triggerInfo = TriggerInfo({
+            triggerId: triggerId,
+            creator: trigger.creator,
+            data: trigger.data
+        });
+
+        emit NewTrigger(abi.encode(triggerInfo));
+    }
+
+    /**
+     * @dev Get a single trigger by triggerId.
+     * @param triggerId The ID of the trigger.
+     * @return _triggerInfo The trigger info.
+     */
     function getTrigger(
         TriggerId triggerId
-    ) external view override returns (TriggerInfo memory _triggerInfo) {
+    ) external view returns (TriggerInfo memory _triggerInfo) {
         Trigger storage _trigger = triggersById[triggerId];
         _triggerInfo = TriggerInfo({
             triggerId: triggerId,
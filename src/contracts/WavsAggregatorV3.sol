@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: MIT
+pragma solidity 0.8.22;
+
+import {AggregatorV3Interface} from "interfaces/IAggregatorV3.sol";
+import {IPriceFeed} from "interfaces/IPriceFeed.sol";
+import {ISimpleSubmit} from "interfaces/IWavsSubmit.sol";
+import {ITypes} from "interfaces/ITypes.sol";
+
+/// @notice Exposes the `aggregator_v3` rounds stored in a SimpleSubmit through AggregatorV3Interface
+/// @dev The SimpleSubmit must only receive rounds of this feed, each round being its TriggerId
+contract WavsAggregatorV3 is AggregatorV3Interface {
+    /// @notice Submission contract holding the rounds
+    ISimpleSubmit public immutable submit;
+    /// @notice Decimals the oracle scales answers to (WAVS_ENV_PRICE_DECIMALS)
+    uint8 internal immutable _decimals;
+    /// @notice Description of the feed
+    string internal _description;
+
+    /**
+     * @notice Initialize the contract
+     * @param _submit The submission contract holding the rounds
+     * @param decimals_ The decimals the oracle scales answers to
+     * @param description_ The description of the feed (e.g. ETH / USD)
+     */
+    constructor(ISimpleSubmit _submit, uint8 decimals_, string memory description_) {
+        submit = _submit;
+        _decimals = decimals_;
+        _description = description_;
+    }
+
+    /// @inheritdoc AggregatorV3Interface
+    function decimals() external view returns (uint8) {
+        return _decimals;
+    }
+
+    /// @inheritdoc AggregatorV3Interface
+    function description() external view returns (string memory) {
+        return _description;
+    }
+
+    /// @inheritdoc AggregatorV3Interface
+    function version() external pure returns (uint256) {
+        return 1;
+    }
+
+    /// @inheritdoc AggregatorV3Interface
+    function getRoundData(uint80 _roundId)
+        public
+        view
+        returns (uint80 roundId, int256 answer, uint256 startedAt, uint256 updatedAt, uint80 answeredInRound)
+    {
+        require(_roundId <= type(uint64).max, "No data present");
+        ITypes.TriggerId _triggerId = ITypes.TriggerId.wrap(uint64(_roundId));
+        require(submit.isValidTriggerId(_triggerId), "No data present");
+
+        IPriceFeed.RoundData memory _round = abi.decode(submit.getData(_triggerId), (IPriceFeed.RoundData));
+        return (_round.roundId, _round.answer, _round.startedAt, _round.updatedAt, _round.answeredInRound);
+    }
+
+    /// @inheritdoc AggregatorV3Interface
+    function latestRoundData()
+        external
+        view
+        returns (uint80 roundId, int256 answer, uint256 startedAt, uint256 updatedAt, uint80 answeredInRound)
+    {
+        return getRoundData(ITypes.TriggerId.unwrap(submit.latestTriggerId()));
+    }
+}
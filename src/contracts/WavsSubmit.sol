@@ -12,6 +12,10 @@ contract SimpleSubmit is ISimpleSubmit, IWavsServiceHandler {
     mapping(TriggerId _triggerId => bytes _data) internal _datas;
     /// @notice Mapping of trigger signatures
     mapping(TriggerId _triggerId => bytes _signature) internal _signatures;
+    /// @notice Mapping of trigger submission timestamps
+    mapping(TriggerId _triggerId => uint64 _submittedAt) internal _submittedAts;
+    /// @notice TriggerId of the latest submission
+    TriggerId internal _latestTriggerId;
 
     /// @notice Service manager instance
     IWavsServiceManager private _serviceManager;
@@ -33,6 +37,8 @@ contract SimpleSubmit is ISimpleSubmit, IWavsServiceHandler {
         _signatures[dataWithId.triggerId] = _signature;
         _datas[dataWithId.triggerId] = dataWithId.data;
         _validTriggers[dataWithId.triggerId] = true;
+        _submittedAts[dataWithId.triggerId] = uint64(block.timestamp);
+        _latestTriggerId = dataWithId.triggerId;
     }
 
     /// @inheritdoc ISimpleSubmit
@@ -49,4 +55,14 @@ contract SimpleSubmit is ISimpleSubmit, IWavsServiceHandler {
     function getData(TriggerId _triggerId) external view returns (bytes memory _data) {
         _data = _datas[_triggerId];
     }
+
+    /// @inheritdoc ISimpleSubmit
+    function getSubmittedAt(TriggerId _triggerId) external view returns (uint64 _submittedAt) {
+        _submittedAt = _submittedAts[_triggerId];
+    }
+
+    /// @inheritdoc ISimpleSubmit
+    function latestTriggerId() external view returns (TriggerId _triggerId) {
+        _triggerId = _latestTriggerId;
+    }
 }
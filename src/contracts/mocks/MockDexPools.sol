@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: MIT
+pragma solidity 0.8.22;
+
+/// @notice Token exposing only the metadata read by the dex price source
+contract MockToken {
+    /// @notice Symbol of the token
+    string public symbol;
+    /// @notice Decimals of the token
+    uint8 public decimals;
+
+    /**
+     * @notice Initialize the contract
+     * @param _symbol The symbol of the token
+     * @param _decimals The decimals of the token
+     */
+    constructor(string memory _symbol, uint8 _decimals) {
+        symbol = _symbol;
+        decimals = _decimals;
+    }
+}
+
+/// @notice Uniswap V2 pair with settable reserves, for testing the dex price source on anvil
+contract MockUniswapV2Pair {
+    /// @notice First token of the pair
+    address public token0;
+    /// @notice Second token of the pair
+    address public token1;
+
+    uint112 internal _reserve0;
+    uint112 internal _reserve1;
+    uint32 internal _blockTimestampLast;
+
+    /**
+     * @notice Initialize the contract
+     * @param _token0 The first token of the pair
+     * @param _token1 The second token of the pair
+     */
+    constructor(address _token0, address _token1) {
+        token0 = _token0;
+        token1 = _token1;
+    }
+
+    /**
+     * @notice Set the reserves of the pair
+     * @param reserve0 The reserve of token0, in its smallest unit
+     * @param reserve1 The reserve of token1, in its smallest unit
+     */
+    function setReserves(uint112 reserve0, uint112 reserve1) external {
+        _reserve0 = reserve0;
+        _reserve1 = reserve1;
+        _blockTimestampLast = uint32(block.timestamp);
+    }
+
+    /// @notice Same as IUniswapV2Pair.getReserves
+    function getReserves() external view returns (uint112 reserve0, uint112 reserve1, uint32 blockTimestampLast) {
+        return (_reserve0, _reserve1, _blockTimestampLast);
+    }
+}
+
+/// @notice Uniswap V3 pool with a settable price, for testing the dex price source on anvil
+contract MockUniswapV3Pool {
+    /// @notice First token of the pool
+    address public token0;
+    /// @notice Second token of the pool
+    address public token1;
+
+    uint160 internal _sqrtPriceX96;
+
+    /**
+     * @notice Initialize the contract
+     * @param _token0 The first token of the pool
+     * @param _token1 The second token of the pool
+     */
+    constructor(address _token0, address _token1) {
+        token0 = _token0;
+        token1 = _token1;
+    }
+
+    /**
+     * @notice Set the price of the pool
+     * @param sqrtPriceX96 The square root of the price of token0 in token1, as a Q64.96
+     */
+    function setSqrtPriceX96(uint160 sqrtPriceX96) external {
+        _sqrtPriceX96 = sqrtPriceX96;
+    }
+
+    /// @notice Same as IUniswapV3Pool.slot0, only sqrtPriceX96 is set
+    function slot0()
+        external
+        view
+        returns (
+            uint160 sqrtPriceX96,
+            int24 tick,
+            uint16 observationIndex,
+            uint16 observationCardinality,
+            uint16 observationCardinalityNext,
+            uint8 feeProtocol,
+            bool unlocked
+        )
+    {
+        return (_sqrtPriceX96, 0, 0, 1, 1, 0, true);
+    }
+}
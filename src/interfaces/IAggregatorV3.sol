@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: MIT
+pragma solidity 0.8.22;
+
+/// @notice Same ABI as Chainlink's AggregatorV3Interface so existing consumers can read WAVS prices
+interface AggregatorV3Interface {
+    /**
+     * @notice Get the number of decimals of the answer
+     * @return _decimals The number of decimals
+     */
+    function decimals() external view returns (uint8 _decimals);
+
+    /**
+     * @notice Get the description of the feed
+     * @return _description The description (e.g. ETH / USD)
+     */
+    function description() external view returns (string memory _description);
+
+    /**
+     * @notice Get the version of the aggregator
+     * @return _version The version
+     */
+    function version() external view returns (uint256 _version);
+
+    /**
+     * @notice Get the data of a round
+     * @param _roundId The identifier of the round
+     * @return roundId The identifier of the round
+     * @return answer The price
+     * @return startedAt The timestamp the round started at
+     * @return updatedAt The timestamp the round was updated at
+     * @return answeredInRound The round the answer was computed in
+     */
+    function getRoundData(uint80 _roundId)
+        external
+        view
+        returns (uint80 roundId, int256 answer, uint256 startedAt, uint256 updatedAt, uint80 answeredInRound);
+
+    /**
+     * @notice Get the data of the latest round
+     * @return roundId The identifier of the round
+     * @return answer The price
+     * @return startedAt The timestamp the round started at
+     * @return updatedAt The timestamp the round was updated at
+     * @return answeredInRound The round the answer was computed in
+     */
+    function latestRoundData()
+        external
+        view
+        returns (uint80 roundId, int256 answer, uint256 startedAt, uint256 updatedAt, uint80 answeredInRound);
+}
@@ -0,0 +1,12 @@
+// SPDX-License-Identifier: MIT
+pragma solidity 0.8.22;
+
+/// @notice The part of Gnosis ConditionalTokens the prediction market controller reports to
+interface IConditionalTokens {
+    /**
+     * @notice Report the payouts of a condition the caller is the oracle of
+     * @param questionId The question the condition was prepared for
+     * @param payouts Payout numerators for each outcome slot
+     */
+    function reportPayouts(bytes32 questionId, uint256[] calldata payouts) external;
+}
@@ -0,0 +1,138 @@
+// SPDX-License-Identifier: MIT
+pragma solidity 0.8.22;
+
+interface IPriceFeed {
+    /**
+     * @notice Struct to store the price of a single asset
+     * @param id CoinMarketCap id of the asset (e.g. 1027 for ETH)
+     * @param symbol Ticker symbol of the asset (e.g. ETH)
+     * @param currency Quote currency of the price (e.g. USD, EUR or BTC)
+     * @param price Median price across the agreeing sources, scaled by 10 ** decimals
+     * @param conf Half-width of the band the price is expected to lie in, scaled by 10 ** decimals
+     * @param decimals Number of decimals used to scale the price
+     * @param timestamp Unix timestamp (seconds) of the oldest quote used
+     * @param sources Number of price sources that agreed on the price
+     * @param confMethod Derivation of conf: 0 spread between the sources, 1 recent chart volatility
+     * @param derived True when the price is a cross rate through USD rather than a direct quote
+     */
+    struct PriceData {
+        uint64 id;
+        string symbol;
+        string currency;
+        uint256 price;
+        uint256 conf;
+        uint8 decimals;
+        uint64 timestamp;
+        uint8 sources;
+        uint8 confMethod;
+        bool derived;
+    }
+
+    /**
+     * @notice Struct to store the time-weighted average price of a single asset
+     * @param id CoinMarketCap id of the asset (e.g. 1027 for ETH)
+     * @param symbol Ticker symbol of the asset (e.g. ETH)
+     * @param price Time-weighted average price in USD, scaled by 10 ** decimals
+     * @param decimals Number of decimals used to scale the price
+     * @param samples Number of historical points used
+     * @param windowStart Unix timestamp (seconds) of the first point used
+     * @param windowEnd Unix timestamp (seconds) of the last point used
+     */
+    struct TwapData {
+        uint64 id;
+        string symbol;
+        uint256 price;
+        uint8 decimals;
+        uint32 samples;
+        uint64 windowStart;
+        uint64 windowEnd;
+    }
+
+    /**
+     * @notice Struct to store a round in the layout of Chainlink's AggregatorV3Interface
+     * @param roundId TriggerId the round was submitted for
+     * @param answer Price in the quote currency of the feed (`convert`, USD by default), scaled by
+     * 10 ** decimals of the oracle configuration
+     * @param startedAt Unix timestamp (seconds) of the oldest quote used
+     * @param updatedAt Unix timestamp (seconds) at which the quotes were aggregated
+     * @param answeredInRound TriggerId the answer was computed in, equal to roundId
+     */
+    struct RoundData {
+        uint80 roundId;
+        int256 answer;
+        uint256 startedAt;
+        uint256 updatedAt;
+        uint80 answeredInRound;
+    }
+
+    /**
+     * @notice Struct to store the price of a single asset at a past timestamp
+     * @param id CoinMarketCap id of the asset (e.g. 1027 for ETH)
+     * @param symbol Ticker symbol of the asset (e.g. ETH)
+     * @param price Price in USD of the sample closest to the target, scaled by 10 ** decimals
+     * @param decimals Number of decimals used to scale the price
+     * @param targetTimestamp Unix timestamp (seconds) that was requested
+     * @param sampleTimestamp Unix timestamp (seconds) of the sample used
+     * @param distance Distance in seconds between the sample and the target
+     */
+    struct HistoricalPriceData {
+        uint64 id;
+        string symbol;
+        uint256 price;
+        uint8 decimals;
+        uint64 targetTimestamp;
+        uint64 sampleTimestamp;
+        uint64 distance;
+    }
+
+    /**
+     * @notice Struct to store the market statistics of a single asset
+     * @param version Layout version of the struct, currently 1
+     * @param id CoinMarketCap id of the asset (e.g. 1027 for ETH)
+     * @param symbol Ticker symbol of the asset (e.g. ETH)
+     * @param timestamp Unix timestamp (seconds) of the statistics
+     * @param decimals Number of decimals used to scale the USD amounts and supplies
+     * @param fields Bitmask of the requested statistics: 1 market cap, 2 volume, 4 circulating
+     * supply, 8 total supply, 16 change. Statistics that weren't requested are zero
+     * @param price Price in USD, scaled by 10 ** decimals
+     * @param marketCap Market cap in USD, scaled by 10 ** decimals
+     * @param volume24h Traded volume over the last 24 hours in USD, scaled by 10 ** decimals
+     * @param circulatingSupply Circulating supply, scaled by 10 ** decimals
+     * @param totalSupply Total supply, scaled by 10 ** decimals
+     * @param change24hBps Price change over the last 24 hours, in basis points
+     */
+    struct MarketStatsData {
+        uint8 version;
+        uint64 id;
+        string symbol;
+        uint64 timestamp;
+        uint8 decimals;
+        uint32 fields;
+        uint256 price;
+        uint256 marketCap;
+        uint256 volume24h;
+        uint256 circulatingSupply;
+        uint256 totalSupply;
+        int32 change24hBps;
+    }
+
+    /**
+     * @notice Struct to store the annualized realized volatility of a single asset
+     * @param id CoinMarketCap id of the asset (e.g. 1027 for ETH)
+     * @param symbol Ticker symbol of the asset (e.g. ETH)
+     * @param volatility Annualized volatility of the log returns, scaled by 10 ** decimals (1.0 is 100%)
+     * @param decimals Number of decimals used to scale the volatility
+     * @param samples Number of historical points used
+     * @param windowStart Unix timestamp (seconds) of the first point used
+     * @param windowEnd Unix timestamp (seconds) of the last point used
+     */
+    struct VolatilityData {
+        uint64 id;
+        string symbol;
+        uint256 volatility;
+        uint8 decimals;
+        uint32 samples;
+        uint64 windowStart;
+        uint64 windowEnd;
+    }
+}
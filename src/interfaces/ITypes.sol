@@ -24,6 +24,38 @@ interface ITypes {
         bytes data;
     }
 
+    /**
+     * @notice State of a market resolution reported by the AVS
+     * @dev Resolved carries payouts, NotReady asks for the trigger to be re-armed after
+     * retryAfter, Invalid reports a market that can't be resolved from its script and data,
+     * Disagreement reports data providers disagreeing on the result and is retried like NotReady
+     */
+    enum ResolutionStatus {
+        Resolved,
+        NotReady,
+        Invalid,
+        Disagreement
+    }
+
+    /**
+     * @notice Struct to store the resolution of a prediction market
+     * @param status Whether the market was resolved, is not ready yet or is invalid
+     * @param questionId Identifier of the question, as passed to ConditionalTokens.reportPayouts
+     * @param payouts Payout numerators for each outcome slot, empty unless resolved
+     * @param startTime Unix timestamp (seconds) at which the event is scheduled to start, 0 if unknown
+     * @param retryAfter Unix timestamp (seconds) after which to trigger again, 0 unless not ready or
+     * disputed by the providers
+     * @param reason Why the market isn't resolved, empty when resolved
+     */
+    struct AvsOutputData {
+        ResolutionStatus status;
+        bytes32 questionId;
+        uint256[] payouts;
+        uint64 startTime;
+        uint64 retryAfter;
+        string reason;
+    }
+
     /**
      * @notice Event emitted when a new trigger is created
      * @param _triggerInfo Encoded TriggerInfo struct
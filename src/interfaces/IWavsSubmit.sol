@@ -27,4 +27,17 @@ interface ISimpleSubmit is ITypes {
      * @return _data The data associated with the trigger
      */
     function getData(TriggerId _triggerId) external view returns (bytes memory _data);
+
+    /**
+     * @notice Get the time the data for a triggerId was submitted
+     * @param _triggerId The identifier of the trigger
+     * @return _submittedAt The block timestamp of the submission, zero if nothing was submitted
+     */
+    function getSubmittedAt(TriggerId _triggerId) external view returns (uint64 _submittedAt);
+
+    /**
+     * @notice Get the triggerId of the latest submission
+     * @return _triggerId The latest triggerId, zero if nothing was submitted yet
+     */
+    function latestTriggerId() external view returns (TriggerId _triggerId);
 }
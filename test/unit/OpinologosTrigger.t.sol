@@ -0,0 +1,124 @@
+// SPDX-License-Identifier: MIT
+pragma solidity 0.8.22;
+
+import {PredictionMarketOracleController} from "contracts/OpinologosTrigger.sol";
+import {IConditionalTokens} from "interfaces/IConditionalTokens.sol";
+import {ITypes} from "interfaces/ITypes.sol";
+import {MockServiceManager} from "../utils/MockServiceManager.sol";
+
+contract OpinologosTriggerTest is MockServiceManager {
+    bytes32 internal constant QUESTION = keccak256("question");
+
+    PredictionMarketOracleController public controller;
+    address internal conditionalTokens = makeAddr("conditionalTokens");
+
+    function setUp() public override {
+        super.setUp();
+        // Payouts are accepted, only the controller is under test
+        vm.etch(conditionalTokens, hex"00");
+        vm.mockCall(conditionalTokens, abi.encodeWithSelector(IConditionalTokens.reportPayouts.selector), "");
+
+        controller = new PredictionMarketOracleController(serviceManager, conditionalTokens);
+    }
+
+    function testAddTriggerAppendsQuestion() public {
+        ITypes.TriggerId triggerId = controller.addTrigger(QUESTION, "sr:sport_event:1|KEY");
+
+        ITypes.TriggerInfo memory trigger = controller.getTrigger(triggerId);
+        assertEq(trigger.creator, address(this));
+        assertEq(trigger.data, abi.encodePacked("sr:sport_event:1|KEY|question=", vm.toString(QUESTION)));
+        assertEq(controller.questionIdById(triggerId), QUESTION);
+    }
+
+    function testAddTriggerOnlyOwner() public {
+        vm.prank(makeAddr("stranger"));
+        vm.expectRevert("Only the owner can add triggers");
+        controller.addTrigger(QUESTION, "sr:sport_event:1|KEY");
+    }
+
+    function testResolvedReportsPayouts() public {
+        ITypes.TriggerId triggerId = controller.addTrigger(QUESTION, "sr:sport_event:1|KEY");
+        uint256[] memory payouts = new uint256[](3);
+        payouts[0] = 1;
+
+        vm.expectCall(conditionalTokens, abi.encodeCall(IConditionalTokens.reportPayouts, (QUESTION, payouts)));
+        _submit(triggerId, _output(ITypes.ResolutionStatus.Resolved, QUESTION, payouts, 0));
+
+        assertEq(controller.retryAfterById(triggerId), 0);
+    }
+
+    function testRejectsOtherQuestion() public {
+        ITypes.TriggerId triggerId = controller.addTrigger(QUESTION, "sr:sport_event:1|KEY");
+        uint256[] memory payouts = new uint256[](2);
+        payouts[1] = 1;
+
+        vm.expectRevert("Question does not match trigger");
+        _submit(triggerId, _output(ITypes.ResolutionStatus.Resolved, keccak256("other"), payouts, 0));
+    }
+
+    function testNotReadyRearmsAfterRetryTime() public {
+        ITypes.TriggerId triggerId = controller.addTrigger(QUESTION, "sr:sport_event:1|KEY");
+        uint64 retryAfter = uint64(block.timestamp + 1 hours);
+
+        vm.expectEmit(true, true, false, true, address(controller));
+        emit PredictionMarketOracleController.ResolutionDeferred(triggerId, QUESTION, 0, retryAfter, "reason");
+        _submit(triggerId, _output(ITypes.ResolutionStatus.NotReady, QUESTION, new uint256[](0), retryAfter));
+        assertEq(controller.retryAfterById(triggerId), retryAfter);
+
+        vm.expectRevert("Too early to retry");
+        controller.rearmTrigger(triggerId);
+
+        vm.warp(retryAfter);
+        ITypes.TriggerInfo memory trigger = controller.getTrigger(triggerId);
+        vm.expectEmit(false, false, false, true, address(controller));
+        emit ITypes.NewTrigger(abi.encode(trigger));
+        controller.rearmTrigger(triggerId);
+        assertEq(controller.retryAfterById(triggerId), 0);
+
+        vm.expectRevert("Trigger is not waiting for a retry");
+        controller.rearmTrigger(triggerId);
+    }
+
+    function testDisagreementDefers() public {
+        ITypes.TriggerId triggerId = controller.addTrigger(QUESTION, "sr:sport_event:1|KEY");
+        uint64 retryAfter = uint64(block.timestamp + 15 minutes);
+
+        vm.expectEmit(true, true, false, true, address(controller));
+        emit PredictionMarketOracleController.ResolutionDisagreement(triggerId, QUESTION, retryAfter, "reason");
+        _submit(triggerId, _output(ITypes.ResolutionStatus.Disagreement, QUESTION, new uint256[](0), retryAfter));
+
+        assertEq(controller.retryAfterById(triggerId), retryAfter);
+    }
+
+    function testInvalidIsNotRetried() public {
+        ITypes.TriggerId triggerId = controller.addTrigger(QUESTION, "sr:sport_event:1|KEY");
+
+        vm.expectEmit(true, true, false, true, address(controller));
+        emit PredictionMarketOracleController.ResolutionInvalid(triggerId, QUESTION, "reason");
+        _submit(triggerId, _output(ITypes.ResolutionStatus.Invalid, QUESTION, new uint256[](0), 0));
+
+        assertEq(controller.retryAfterById(triggerId), 0);
+        vm.expectRevert("Trigger is not waiting for a retry");
+        controller.rearmTrigger(triggerId);
+    }
+
+    function _output(ITypes.ResolutionStatus status, bytes32 questionId, uint256[] memory payouts, uint64 retryAfter)
+        internal
+        pure
+        returns (ITypes.AvsOutputData memory)
+    {
+        return ITypes.AvsOutputData({
+            status: status,
+            questionId: questionId,
+            payouts: payouts,
+            startTime: 0,
+            retryAfter: retryAfter,
+            reason: "reason"
+        });
+    }
+
+    function _submit(ITypes.TriggerId triggerId, ITypes.AvsOutputData memory output) internal {
+        ITypes.DataWithId memory dataWithId = ITypes.DataWithId({triggerId: triggerId, data: abi.encode(output)});
+        controller.handleSignedData(abi.encode(dataWithId), "signature");
+    }
+}
@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: MIT
+pragma solidity 0.8.22;
+
+import {IWavsServiceManager} from "@wavs/interfaces/IWavsServiceManager.sol";
+import {SimpleSubmit} from "contracts/WavsSubmit.sol";
+import {WavsAggregatorV3} from "contracts/WavsAggregatorV3.sol";
+import {IPriceFeed} from "interfaces/IPriceFeed.sol";
+import {ITypes} from "interfaces/ITypes.sol";
+import {MockServiceManager} from "../utils/MockServiceManager.sol";
+
+contract AggregatorV3Test is MockServiceManager {
+    SimpleSubmit public simpleSubmit;
+    WavsAggregatorV3 public aggregator;
+
+    function setUp() public override {
+        super.setUp();
+        simpleSubmit = new SimpleSubmit(IWavsServiceManager(serviceManager));
+        aggregator = new WavsAggregatorV3(simpleSubmit, 8, "ETH / USD");
+    }
+
+    function testMetadata() public view {
+        assertEq(aggregator.decimals(), 8);
+        assertEq(aggregator.description(), "ETH / USD");
+        assertEq(aggregator.version(), 1);
+    }
+
+    function testRounds() public {
+        _submitRound(1, 3000e8);
+        _submitRound(2, 3100e8);
+
+        (uint80 roundId, int256 answer, uint256 startedAt, uint256 updatedAt, uint80 answeredInRound) =
+            aggregator.latestRoundData();
+        assertEq(roundId, 2);
+        assertEq(answer, 3100e8);
+        assertEq(startedAt, 1000);
+        assertEq(updatedAt, 1060);
+        assertEq(answeredInRound, 2);
+
+        (roundId, answer,,,) = aggregator.getRoundData(1);
+        assertEq(roundId, 1);
+        assertEq(answer, 3000e8);
+    }
+
+    function testMissingRound() public {
+        vm.expectRevert("No data present");
+        aggregator.latestRoundData();
+
+        _submitRound(1, 3000e8);
+        vm.expectRevert("No data present");
+        aggregator.getRoundData(2);
+        vm.expectRevert("No data present");
+        aggregator.getRoundData(uint80(type(uint64).max) + 1);
+    }
+
+    function _submitRound(uint64 triggerId, int256 answer) internal {
+        IPriceFeed.RoundData memory round = IPriceFeed.RoundData({
+            roundId: triggerId,
+            answer: answer,
+            startedAt: 1000,
+            updatedAt: 1060,
+            answeredInRound: triggerId
+        });
+        ITypes.DataWithId memory dataWithId =
+            ITypes.DataWithId({triggerId: ITypes.TriggerId.wrap(triggerId), data: abi.encode(round)});
+        simpleSubmit.handleSignedData(abi.encode(dataWithId), "signature");
+    }
+}
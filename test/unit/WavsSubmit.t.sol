@@ -0,0 +1,45 @@
+// SPDX-License-Identifier: MIT
+pragma solidity 0.8.22;
+
+import {IWavsServiceManager} from "@wavs/interfaces/IWavsServiceManager.sol";
+import {SimpleSubmit} from "contracts/WavsSubmit.sol";
+import {ITypes} from "interfaces/ITypes.sol";
+import {MockServiceManager} from "../utils/MockServiceManager.sol";
+
+contract SubmitTest is MockServiceManager {
+    SimpleSubmit public simpleSubmit;
+
+    function setUp() public override {
+        super.setUp();
+        simpleSubmit = new SimpleSubmit(IWavsServiceManager(serviceManager));
+    }
+
+    function testLatestTriggerId() public {
+        assertEq(ITypes.TriggerId.unwrap(simpleSubmit.latestTriggerId()), 0);
+
+        _submit(2, "data2");
+        _submit(1, "data1");
+
+        assertEq(ITypes.TriggerId.unwrap(simpleSubmit.latestTriggerId()), 1);
+        assertTrue(simpleSubmit.isValidTriggerId(ITypes.TriggerId.wrap(2)));
+        assertEq(simpleSubmit.getData(ITypes.TriggerId.wrap(1)), "data1");
+        assertEq(simpleSubmit.getSignature(ITypes.TriggerId.wrap(1)), "signature");
+    }
+
+    function testSubmittedAt() public {
+        assertEq(simpleSubmit.getSubmittedAt(ITypes.TriggerId.wrap(1)), 0);
+
+        vm.warp(1_700_000_000);
+        _submit(1, "data1");
+        vm.warp(1_700_000_600);
+        _submit(2, "data2");
+
+        assertEq(simpleSubmit.getSubmittedAt(ITypes.TriggerId.wrap(1)), 1_700_000_000);
+        assertEq(simpleSubmit.getSubmittedAt(ITypes.TriggerId.wrap(2)), 1_700_000_600);
+    }
+
+    function _submit(uint64 triggerId, bytes memory data) internal {
+        ITypes.DataWithId memory dataWithId = ITypes.DataWithId({triggerId: ITypes.TriggerId.wrap(triggerId), data: data});
+        simpleSubmit.handleSignedData(abi.encode(dataWithId), "signature");
+    }
+}
@@ -0,0 +1,16 @@
+// SPDX-License-Identifier: MIT
+pragma solidity 0.8.22;
+
+import {Test} from "forge-std/Test.sol";
+import {IWavsServiceManager} from "@wavs/interfaces/IWavsServiceManager.sol";
+
+/// @dev Base of the service handler tests: every signature is valid, only what the handlers do
+/// with the data they're sent is under test
+abstract contract MockServiceManager is Test {
+    address internal serviceManager = makeAddr("serviceManager");
+
+    function setUp() public virtual {
+        vm.etch(serviceManager, hex"00");
+        vm.mockCall(serviceManager, abi.encodeWithSelector(IWavsServiceManager.validate.selector), "");
+    }
+}